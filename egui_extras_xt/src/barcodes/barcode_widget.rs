@@ -1,10 +1,13 @@
-use std::borrow::Borrow;
+use std::borrow::{Borrow, Cow};
+use std::ops::RangeInclusive;
 use std::sync::Arc;
 
 use barcoders::error::Error;
 use egui::util::cache::{ComputerMut, FrameCache};
 use egui::{vec2, Align2, Color32, FontFamily, FontId, Rect, Response, Sense, Stroke, Ui, Widget};
 
+use crate::barcodes::ErrorPlaceholder;
+
 use barcoders::sym::codabar::Codabar;
 use barcoders::sym::code11::Code11;
 use barcoders::sym::code128::Code128;
@@ -57,6 +60,147 @@ pub enum BarcodeKind {
 }
 
 impl BarcodeKind {
+    pub fn validate<T: AsRef<str>>(self, data: T) -> Result<(), Error> {
+        self.encode(data).map(|_| ())
+    }
+
+    #[must_use]
+    pub fn is_valid_char(self, c: char) -> bool {
+        match self {
+            BarcodeKind::Codabar => {
+                matches!(c, '0'..='9' | '-' | '/' | '.' | ':' | '+' | '$' | 'A'..='D')
+            }
+            BarcodeKind::Code11 => matches!(c, '0'..='9' | '-'),
+            BarcodeKind::Code39 | BarcodeKind::Code39Checksum => {
+                matches!(c, '0'..='9' | 'A'..='Z' | '-' | '.' | ' ' | '$' | '/' | '+' | '%')
+            }
+            BarcodeKind::Code93 => matches!(
+                c,
+                '0'..='9' | 'A'..='Z' | '-' | '.' | ' ' | '$' | '/' | '+' | '%' | '(' | ')' | '['
+            ),
+            BarcodeKind::Code128 => matches!(
+                c,
+                '\u{0000}'..='\u{007E}'
+                    | '\u{00C0}'
+                    | '\u{0181}'
+                    | '\u{0106}'
+                    | '\u{00F7}'
+                    | '\u{0179}'..='\u{017D}'
+            ),
+            BarcodeKind::EAN8
+            | BarcodeKind::EAN13
+            | BarcodeKind::EANSUPP
+            | BarcodeKind::ITF
+            | BarcodeKind::STF => c.is_ascii_digit(),
+        }
+    }
+
+    /// Returns the position and value of the first character in `data`
+    /// not accepted by this barcode kind. Code 128 data has to start with a
+    /// character set selector (`À`, `Ɓ` or `Ć`).
+    #[must_use]
+    pub fn invalid_char<T: AsRef<str>>(self, data: T) -> Option<(usize, char)> {
+        data.as_ref().chars().enumerate().find(|&(index, c)| {
+            !self.is_valid_char(c)
+                || ((self == BarcodeKind::Code128)
+                    && (index == 0)
+                    && !matches!(c, '\u{00C0}' | '\u{0181}' | '\u{0106}'))
+        })
+    }
+
+    /// Ranges of accepted data lengths, including the check digit
+    /// when the barcode kind has an optional one. Code 128 has no upper
+    /// limit.
+    #[must_use]
+    pub fn valid_lengths(self) -> &'static [RangeInclusive<usize>] {
+        const SHORT_TEXT: &[RangeInclusive<usize>] = &[1..=256];
+        const CODE128: &[RangeInclusive<usize>] = &[2..=usize::MAX];
+        const EAN8: &[RangeInclusive<usize>] = &[7..=8];
+        const EAN13: &[RangeInclusive<usize>] = &[12..=13];
+        const EANSUPP: &[RangeInclusive<usize>] = &[2..=2, 5..=5];
+
+        match self {
+            BarcodeKind::Codabar
+            | BarcodeKind::Code11
+            | BarcodeKind::Code39
+            | BarcodeKind::Code39Checksum
+            | BarcodeKind::Code93
+            | BarcodeKind::ITF
+            | BarcodeKind::STF => SHORT_TEXT,
+            BarcodeKind::Code128 => CODE128,
+            BarcodeKind::EAN8 => EAN8,
+            BarcodeKind::EAN13 => EAN13,
+            BarcodeKind::EANSUPP => EANSUPP,
+        }
+    }
+
+    #[must_use]
+    pub fn is_valid_length(self, length: usize) -> bool {
+        self.valid_lengths()
+            .iter()
+            .any(|range| range.contains(&length))
+    }
+
+    /// Whether the barcode kind uses a GS1 modulo-10 check digit.
+    #[must_use]
+    pub fn has_check_digit(self) -> bool {
+        matches!(
+            self,
+            BarcodeKind::EAN8 | BarcodeKind::EAN13 | BarcodeKind::ITF | BarcodeKind::STF
+        )
+    }
+
+    /// Computes the check digit for `data` given without its check digit.
+    #[must_use]
+    pub fn check_digit<T: AsRef<str>>(self, data: T) -> Option<char> {
+        let data = data.as_ref();
+
+        if !self.has_check_digit() || data.is_empty() {
+            return None;
+        }
+
+        match self {
+            BarcodeKind::EAN8 if data.len() != 7 => return None,
+            BarcodeKind::EAN13 if data.len() != 12 => return None,
+            _ => {}
+        }
+
+        let weighted_sum = data
+            .chars()
+            .rev()
+            .map(|c| c.to_digit(10))
+            .enumerate()
+            .map(|(index, digit)| digit.map(|d| if index % 2 == 0 { d * 3 } else { d }))
+            .sum::<Option<u32>>()?;
+
+        char::from_digit((10 - (weighted_sum % 10)) % 10, 10)
+    }
+
+    /// Checks the trailing check digit of a complete code.
+    #[must_use]
+    pub fn verify_check_digit<T: AsRef<str>>(self, code: T) -> bool {
+        let code = code.as_ref();
+
+        match code.char_indices().last() {
+            Some((index, last)) => self.check_digit(&code[..index]) == Some(last),
+            None => false,
+        }
+    }
+
+    /// Appends the check digit to `data`. Interleaved 2 of 5 codes are
+    /// zero-padded on the left to keep the digit count even.
+    #[must_use]
+    pub fn with_check_digit<T: AsRef<str>>(self, data: T) -> Option<String> {
+        let data = data.as_ref();
+        let check_digit = self.check_digit(data)?;
+
+        if self == BarcodeKind::ITF && data.len() % 2 == 0 {
+            Some(format!("0{data}{check_digit}"))
+        } else {
+            Some(format!("{data}{check_digit}"))
+        }
+    }
+
     fn encode<T: AsRef<str>>(self, data: T) -> Result<Vec<u8>, Error> {
         match self {
             BarcodeKind::Codabar => Codabar::new(data).map(|b| b.encode()),
@@ -106,6 +250,8 @@ pub struct BarcodeWidget<'a> {
     label_top_margin: f32,
     foreground_color: Color32,
     background_color: Color32,
+    error_placeholder: ErrorPlaceholder,
+    append_check_digit: bool,
 }
 
 impl<'a> BarcodeWidget<'a> {
@@ -122,6 +268,8 @@ impl<'a> BarcodeWidget<'a> {
             label_top_margin: 4.0,
             foreground_color: Color32::BLACK,
             background_color: Color32::WHITE,
+            error_placeholder: ErrorPlaceholder::Detailed,
+            append_check_digit: false,
         }
     }
 
@@ -174,74 +322,221 @@ impl<'a> BarcodeWidget<'a> {
         self.background_color = background_color.into();
         self
     }
-}
 
-impl<'a> Widget for BarcodeWidget<'a> {
-    fn ui(self, ui: &mut Ui) -> Response {
+    pub fn error_placeholder(mut self, error_placeholder: ErrorPlaceholder) -> Self {
+        self.error_placeholder = error_placeholder;
+        self
+    }
+
+    pub fn append_check_digit(mut self, append_check_digit: bool) -> Self {
+        self.append_check_digit = append_check_digit;
+        self
+    }
+
+    fn data(&self) -> Cow<'a, str> {
+        if self.append_check_digit {
+            if let Some(data) = self.barcode_kind.with_check_digit(self.value) {
+                return Cow::Owned(data);
+            }
+        }
+
+        Cow::Borrowed(self.value)
+    }
+
+    pub fn try_ui(self, ui: &mut Ui) -> Result<Response, Error> {
+        let data = self.data();
+        self.show(ui, &data)
+    }
+
+    fn show(self, ui: &mut Ui, data: &str) -> Result<Response, Error> {
         let cached_barcode = ui.memory_mut(|memory| {
             let cache = memory.caches.cache::<BarcodeCache<'_>>();
-            cache.get((self.barcode_kind, self.value))
+            cache.get((self.barcode_kind, data))
         });
 
-        if let Ok(barcode) = cached_barcode.borrow() {
-            let bar_width = self.bar_width as f32 / ui.ctx().pixels_per_point();
-
-            let desired_size = {
-                let mut size = vec2(bar_width * barcode.len() as f32, self.bar_height)
-                    + vec2(self.horizontal_padding, self.vertical_padding) * 2.0;
+        let barcode = match cached_barcode.borrow() {
+            Ok(barcode) => barcode,
+            Err(error) => return Err(*error),
+        };
 
-                if self.label.is_some() {
-                    size += vec2(0.0, self.label_height + self.label_top_margin);
-                }
+        let bar_width = self.bar_width as f32 / ui.ctx().pixels_per_point();
 
-                size
-            };
+        let desired_size = {
+            let mut size = vec2(bar_width * barcode.len() as f32, self.bar_height)
+                + vec2(self.horizontal_padding, self.vertical_padding) * 2.0;
 
-            let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+            if self.label.is_some() {
+                size += vec2(0.0, self.label_height + self.label_top_margin);
+            }
 
-            if ui.is_rect_visible(rect) {
-                ui.painter().rect(
-                    rect,
-                    ui.style().visuals.noninteractive().rounding,
-                    self.background_color,
-                    Stroke::NONE,
+            size
+        };
+
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        if ui.is_rect_visible(rect) {
+            ui.painter().rect(
+                rect,
+                ui.style().visuals.noninteractive().rounding,
+                self.background_color,
+                Stroke::NONE,
+            );
+
+            barcode
+                .iter()
+                .enumerate()
+                .filter(|&(_bar_index, bar_value)| *bar_value == 1)
+                .map(|(bar_index, _bar_value)| {
+                    Rect::from_min_size(
+                        ui.painter().round_pos_to_pixels(
+                            rect.left_top() + vec2(self.horizontal_padding, self.vertical_padding),
+                        ) + vec2(bar_width * bar_index as f32, 0.0),
+                        vec2(bar_width, self.bar_height),
+                    )
+                })
+                .for_each(|bar_rect| {
+                    ui.painter()
+                        .rect(bar_rect, 0.0, self.foreground_color, Stroke::NONE);
+                });
+
+            if let Some(label) = self.label {
+                ui.painter().text(
+                    rect.center_bottom() - vec2(0.0, self.vertical_padding),
+                    Align2::CENTER_BOTTOM,
+                    label,
+                    FontId::new(self.label_height, FontFamily::Proportional),
+                    self.foreground_color,
                 );
+            }
+        }
+
+        Ok(response)
+    }
+}
 
-                barcode
-                    .iter()
-                    .enumerate()
-                    .filter(|&(_bar_index, bar_value)| *bar_value == 1)
-                    .map(|(bar_index, _bar_value)| {
-                        Rect::from_min_size(
-                            ui.painter().round_pos_to_pixels(
-                                rect.left_top()
-                                    + vec2(self.horizontal_padding, self.vertical_padding),
-                            ) + vec2(bar_width * bar_index as f32, 0.0),
-                            vec2(bar_width, self.bar_height),
-                        )
-                    })
-                    .for_each(|bar_rect| {
-                        ui.painter()
-                            .rect(bar_rect, 0.0, self.foreground_color, Stroke::NONE);
-                    });
-
-                if let Some(label) = self.label {
-                    ui.painter().text(
-                        rect.center_bottom() - vec2(0.0, self.vertical_padding),
-                        Align2::CENTER_BOTTOM,
-                        label,
-                        FontId::new(self.label_height, FontFamily::Proportional),
-                        self.foreground_color,
-                    );
-                }
+impl<'a> Widget for BarcodeWidget<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let barcode_kind = self.barcode_kind;
+        let error_placeholder = self.error_placeholder;
+        let data = self.data();
+
+        self.show(ui, &data).unwrap_or_else(|error| {
+            let subject = format!("{barcode_kind} barcode");
+
+            match (error, barcode_kind.invalid_char(&data)) {
+                (Error::Character, Some((_, c))) => error_placeholder.show(
+                    ui,
+                    &subject,
+                    format!("Character {c:?} is not allowed in {barcode_kind}"),
+                ),
+                (Error::Length, _) => error_placeholder.show(
+                    ui,
+                    &subject,
+                    format!(
+                        "Length {} is not allowed in {barcode_kind}",
+                        data.chars().count()
+                    ),
+                ),
+                _ => error_placeholder.show(ui, &subject, error),
             }
+        })
+    }
+}
 
-            response
-        } else {
-            ui.colored_label(
-                ui.style().visuals.error_fg_color,
-                "\u{1F525} Failed to render barcode",
-            )
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_digit() {
+        assert_eq!(BarcodeKind::EAN13.check_digit("400638133393"), Some('1'));
+        assert_eq!(BarcodeKind::EAN8.check_digit("7351353"), Some('7'));
+        assert_eq!(BarcodeKind::ITF.check_digit("1234567"), Some('0'));
+        assert_eq!(BarcodeKind::STF.check_digit("123456"), Some('5'));
+
+        assert_eq!(BarcodeKind::EAN13.check_digit("40063813339"), None);
+        assert_eq!(BarcodeKind::EAN8.check_digit("735135a"), None);
+        assert_eq!(BarcodeKind::ITF.check_digit(""), None);
+        assert_eq!(BarcodeKind::Code39.check_digit("1234567"), None);
+    }
+
+    #[test]
+    fn verify_check_digit() {
+        assert!(BarcodeKind::EAN13.verify_check_digit("4006381333931"));
+        assert!(BarcodeKind::EAN8.verify_check_digit("73513537"));
+        assert!(!BarcodeKind::EAN13.verify_check_digit("4006381333932"));
+        assert!(!BarcodeKind::EAN13.verify_check_digit("400638133393"));
+        assert!(!BarcodeKind::EAN13.verify_check_digit(""));
+    }
+
+    #[test]
+    fn with_check_digit() {
+        assert_eq!(
+            BarcodeKind::EAN13.with_check_digit("400638133393"),
+            Some("4006381333931".to_owned())
+        );
+        assert_eq!(
+            BarcodeKind::ITF.with_check_digit("1234567"),
+            Some("12345670".to_owned())
+        );
+        assert_eq!(
+            BarcodeKind::ITF.with_check_digit("123456"),
+            Some("01234565".to_owned())
+        );
+        assert_eq!(
+            BarcodeKind::STF.with_check_digit("123456"),
+            Some("1234565".to_owned())
+        );
+        assert_eq!(BarcodeKind::Code128.with_check_digit("123456"), None);
+
+        for data in ["400638133393", "123456789012"] {
+            let code = BarcodeKind::EAN13.with_check_digit(data).unwrap();
+            assert!(BarcodeKind::EAN13.verify_check_digit(&code));
+            assert!(BarcodeKind::EAN13.validate(&code).is_ok());
+        }
+    }
+
+    #[test]
+    fn invalid_char() {
+        assert_eq!(BarcodeKind::Code39.invalid_char("ABC-12"), None);
+        assert_eq!(BarcodeKind::Code39.invalid_char("ABc"), Some((2, 'c')));
+        assert_eq!(BarcodeKind::EAN8.invalid_char("12x4"), Some((2, 'x')));
+        assert_eq!(BarcodeKind::Code128.invalid_char("\u{0106}a~"), None);
+        assert_eq!(
+            BarcodeKind::Code128.invalid_char("\u{0181}a\u{007F}"),
+            Some((2, '\u{007F}'))
+        );
+
+        // Code 128 data has to start with a character set selector
+        assert_eq!(BarcodeKind::Code128.invalid_char("abc"), Some((0, 'a')));
+        assert_eq!(BarcodeKind::Code128.validate("abc"), Err(Error::Character));
+        assert!(BarcodeKind::Code128.validate("\u{0181}abc").is_ok());
+    }
+
+    #[test]
+    fn valid_lengths_match_encoder() {
+        for barcode_kind in [
+            BarcodeKind::Code11,
+            BarcodeKind::Code39,
+            BarcodeKind::Code93,
+            BarcodeKind::EANSUPP,
+            BarcodeKind::ITF,
+            BarcodeKind::STF,
+        ] {
+            for length in 0..=300 {
+                let data = "0".repeat(length);
+
+                assert_eq!(
+                    barcode_kind.is_valid_length(length),
+                    barcode_kind.validate(&data) != Err(Error::Length),
+                    "{barcode_kind} with length {length}"
+                );
+            }
         }
+
+        assert!(BarcodeKind::Code128.is_valid_length(1000));
+        assert!(!BarcodeKind::Code128.is_valid_length(1));
     }
 }
@@ -8,6 +8,8 @@ use datamatrix::data::DataEncodingError;
 use datamatrix::placement::Bitmap;
 use datamatrix::{DataMatrix, SymbolList};
 
+use crate::barcodes::ErrorPlaceholder;
+
 // ----------------------------------------------------------------------------
 
 type DataMatrixCacheKey<'a> = &'a str;
@@ -29,6 +31,15 @@ type DataMatrixCache<'a> = FrameCache<DataMatrixCacheValue, DataMatrixComputer>;
 
 // ----------------------------------------------------------------------------
 
+fn describe_error(error: &DataEncodingError) -> &'static str {
+    match error {
+        DataEncodingError::TooMuchOrIllegalData => "Too much or illegal data",
+        DataEncodingError::SymbolListEmpty => "Symbol list is empty",
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 #[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
 pub struct DataMatrixWidget<'a> {
     value: &'a str,
@@ -36,6 +47,7 @@ pub struct DataMatrixWidget<'a> {
     quiet_zone: usize,
     foreground_color: Color32,
     background_color: Color32,
+    error_placeholder: ErrorPlaceholder,
 }
 
 impl<'a> DataMatrixWidget<'a> {
@@ -46,6 +58,7 @@ impl<'a> DataMatrixWidget<'a> {
             quiet_zone: 1,
             foreground_color: Color32::BLACK,
             background_color: Color32::WHITE,
+            error_placeholder: ErrorPlaceholder::Detailed,
         }
     }
 
@@ -68,55 +81,66 @@ impl<'a> DataMatrixWidget<'a> {
         self.background_color = background_color.into();
         self
     }
-}
 
-impl<'a> Widget for DataMatrixWidget<'a> {
-    fn ui(self, ui: &mut Ui) -> Response {
+    pub fn error_placeholder(mut self, error_placeholder: ErrorPlaceholder) -> Self {
+        self.error_placeholder = error_placeholder;
+        self
+    }
+
+    pub fn try_ui(self, ui: &mut Ui) -> Result<Response, DataEncodingError> {
         let cached_bitmap = ui.memory_mut(|memory| {
             let cache = memory.caches.cache::<DataMatrixCache<'_>>();
             cache.get(self.value)
         });
 
-        if let Ok(bitmap) = cached_bitmap.borrow() {
-            let module_size = self.module_size as f32 / ui.ctx().pixels_per_point();
-
-            let desired_size = vec2(
-                (bitmap.width() + self.quiet_zone * 2) as f32,
-                (bitmap.height() + self.quiet_zone * 2) as f32,
-            ) * module_size;
-
-            let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
-
-            if ui.is_rect_visible(rect) {
-                ui.painter().rect(
-                    rect,
-                    ui.style().visuals.noninteractive().rounding,
-                    self.background_color,
-                    Stroke::NONE,
-                );
-
-                bitmap
-                    .pixels()
-                    .map(|(x, y)| {
-                        Rect::from_min_size(
-                            ui.painter().round_pos_to_pixels(
-                                rect.left_top() + Vec2::splat(self.quiet_zone as f32 * module_size),
-                            ) + vec2(x as f32, y as f32) * module_size,
-                            Vec2::splat(module_size),
-                        )
-                    })
-                    .for_each(|module_rect| {
-                        ui.painter()
-                            .rect(module_rect, 0.0, self.foreground_color, Stroke::NONE);
-                    });
-            }
-
-            response
-        } else {
-            ui.colored_label(
-                ui.style().visuals.error_fg_color,
-                "\u{1F525} Failed to render data matrix code",
-            )
+        let bitmap = match cached_bitmap.borrow() {
+            Ok(bitmap) => bitmap,
+            Err(error) => return Err(error.clone()),
+        };
+
+        let module_size = self.module_size as f32 / ui.ctx().pixels_per_point();
+
+        let desired_size = vec2(
+            (bitmap.width() + self.quiet_zone * 2) as f32,
+            (bitmap.height() + self.quiet_zone * 2) as f32,
+        ) * module_size;
+
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        if ui.is_rect_visible(rect) {
+            ui.painter().rect(
+                rect,
+                ui.style().visuals.noninteractive().rounding,
+                self.background_color,
+                Stroke::NONE,
+            );
+
+            bitmap
+                .pixels()
+                .map(|(x, y)| {
+                    Rect::from_min_size(
+                        ui.painter().round_pos_to_pixels(
+                            rect.left_top() + Vec2::splat(self.quiet_zone as f32 * module_size),
+                        ) + vec2(x as f32, y as f32) * module_size,
+                        Vec2::splat(module_size),
+                    )
+                })
+                .for_each(|module_rect| {
+                    ui.painter()
+                        .rect(module_rect, 0.0, self.foreground_color, Stroke::NONE);
+                });
         }
+
+        Ok(response)
+    }
+}
+
+impl<'a> Widget for DataMatrixWidget<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let error_placeholder = self.error_placeholder;
+
+        self.try_ui(ui).unwrap_or_else(|error| {
+            error_placeholder.show(ui, "data matrix code", describe_error(&error))
+        })
     }
 }
@@ -0,0 +1,35 @@
+use std::fmt::Display;
+
+use egui::{Response, Sense, Ui, Vec2};
+use strum::{Display, EnumIter};
+
+// ----------------------------------------------------------------------------
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Display, EnumIter, Eq, PartialEq)]
+pub enum ErrorPlaceholder {
+    #[strum(to_string = "Hidden")]
+    Hidden,
+
+    #[strum(to_string = "Generic")]
+    Generic,
+
+    #[strum(to_string = "Detailed")]
+    Detailed,
+}
+
+impl ErrorPlaceholder {
+    pub(crate) fn show(self, ui: &mut Ui, subject: &str, reason: impl Display) -> Response {
+        match self {
+            ErrorPlaceholder::Hidden => ui.allocate_response(Vec2::ZERO, Sense::hover()),
+            ErrorPlaceholder::Generic => ui.colored_label(
+                ui.style().visuals.error_fg_color,
+                format!("\u{1F525} Failed to render {subject}"),
+            ),
+            ErrorPlaceholder::Detailed => ui.colored_label(
+                ui.style().visuals.error_fg_color,
+                format!("\u{1F525} Failed to render {subject}: {reason}"),
+            ),
+        }
+    }
+}
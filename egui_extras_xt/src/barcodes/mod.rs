@@ -1,7 +1,13 @@
 mod barcode_widget;
 mod datamatrix_widget;
+mod error_placeholder;
 mod qrcode_widget;
 
 pub use barcode_widget::{BarcodeKind, BarcodeWidget};
 pub use datamatrix_widget::DataMatrixWidget;
+pub use error_placeholder::ErrorPlaceholder;
 pub use qrcode_widget::QrCodeWidget;
+
+pub use barcoders::error::Error as BarcodeError;
+pub use datamatrix::data::DataEncodingError;
+pub use qrcode::types::QrError;
@@ -4,8 +4,11 @@ use std::sync::Arc;
 use egui::util::cache::{ComputerMut, FrameCache};
 use egui::{vec2, Color32, Rect, Response, Sense, Stroke, Ui, Vec2, Widget};
 
+use qrcode::types::QrError;
 use qrcode::{Color, QrCode, QrResult};
 
+use crate::barcodes::ErrorPlaceholder;
+
 // ----------------------------------------------------------------------------
 
 type QrCodeCacheKey<'a> = &'a str;
@@ -31,6 +34,7 @@ pub struct QrCodeWidget<'a> {
     quiet_zone: usize,
     foreground_color: Color32,
     background_color: Color32,
+    error_placeholder: ErrorPlaceholder,
 }
 
 impl<'a> QrCodeWidget<'a> {
@@ -41,6 +45,7 @@ impl<'a> QrCodeWidget<'a> {
             quiet_zone: 4,
             foreground_color: Color32::BLACK,
             background_color: Color32::WHITE,
+            error_placeholder: ErrorPlaceholder::Detailed,
         }
     }
 
@@ -63,62 +68,72 @@ impl<'a> QrCodeWidget<'a> {
         self.background_color = background_color.into();
         self
     }
-}
 
-impl<'a> Widget for QrCodeWidget<'a> {
-    fn ui(self, ui: &mut Ui) -> Response {
+    pub fn error_placeholder(mut self, error_placeholder: ErrorPlaceholder) -> Self {
+        self.error_placeholder = error_placeholder;
+        self
+    }
+
+    pub fn try_ui(self, ui: &mut Ui) -> Result<Response, QrError> {
         let cached_qr_code = ui.memory_mut(|memory| {
             let cache = memory.caches.cache::<QrCodeCache<'_>>();
             cache.get(self.value)
         });
 
-        if let Ok(qr_code) = cached_qr_code.borrow() {
-            let module_size = self.module_size as f32 / ui.ctx().pixels_per_point();
-
-            let desired_size =
-                Vec2::splat((qr_code.width() + self.quiet_zone * 2) as f32 * module_size);
-
-            let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
-
-            if ui.is_rect_visible(rect) {
-                ui.painter().rect(
-                    rect,
-                    ui.style().visuals.noninteractive().rounding,
-                    self.background_color,
-                    Stroke::NONE,
-                );
-
-                qr_code
-                    .to_colors()
-                    .into_iter()
-                    .enumerate()
-                    .filter(|&(_module_index, module_value)| module_value == Color::Dark)
-                    .map(|(module_index, _module_value)| {
-                        (
-                            module_index % qr_code.width(),
-                            module_index / qr_code.width(),
-                        )
-                    })
-                    .map(|(x, y)| {
-                        Rect::from_min_size(
-                            ui.painter().round_pos_to_pixels(
-                                rect.left_top() + Vec2::splat(self.quiet_zone as f32 * module_size),
-                            ) + vec2(x as f32, y as f32) * module_size,
-                            Vec2::splat(module_size),
-                        )
-                    })
-                    .for_each(|module_rect| {
-                        ui.painter()
-                            .rect(module_rect, 0.0, self.foreground_color, Stroke::NONE);
-                    });
-            }
-
-            response
-        } else {
-            ui.colored_label(
-                ui.style().visuals.error_fg_color,
-                "\u{1F525} Failed to render QR code",
-            )
+        let qr_code = match cached_qr_code.borrow() {
+            Ok(qr_code) => qr_code,
+            Err(error) => return Err(*error),
+        };
+
+        let module_size = self.module_size as f32 / ui.ctx().pixels_per_point();
+
+        let desired_size =
+            Vec2::splat((qr_code.width() + self.quiet_zone * 2) as f32 * module_size);
+
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        if ui.is_rect_visible(rect) {
+            ui.painter().rect(
+                rect,
+                ui.style().visuals.noninteractive().rounding,
+                self.background_color,
+                Stroke::NONE,
+            );
+
+            qr_code
+                .to_colors()
+                .into_iter()
+                .enumerate()
+                .filter(|&(_module_index, module_value)| module_value == Color::Dark)
+                .map(|(module_index, _module_value)| {
+                    (
+                        module_index % qr_code.width(),
+                        module_index / qr_code.width(),
+                    )
+                })
+                .map(|(x, y)| {
+                    Rect::from_min_size(
+                        ui.painter().round_pos_to_pixels(
+                            rect.left_top() + Vec2::splat(self.quiet_zone as f32 * module_size),
+                        ) + vec2(x as f32, y as f32) * module_size,
+                        Vec2::splat(module_size),
+                    )
+                })
+                .for_each(|module_rect| {
+                    ui.painter()
+                        .rect(module_rect, 0.0, self.foreground_color, Stroke::NONE);
+                });
         }
+
+        Ok(response)
+    }
+}
+
+impl<'a> Widget for QrCodeWidget<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let error_placeholder = self.error_placeholder;
+
+        self.try_ui(ui)
+            .unwrap_or_else(|error| error_placeholder.show(ui, "QR code", error))
     }
 }
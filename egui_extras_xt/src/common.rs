@@ -7,6 +7,8 @@ use epaint::{Color32, Shape, Stroke, TextShape};
 use itertools::Itertools;
 use strum::{Display, EnumIter};
 
+use crate::polygon::concave_polygon;
+
 // ----------------------------------------------------------------------------
 
 #[derive(Clone, Copy, Debug, PartialEq, Display)]
@@ -165,19 +167,8 @@ impl WidgetShape {
             })
             .collect_vec();
 
-        // https://github.com/emilk/egui/issues/513
-        outline_points
-            .iter()
-            .circular_tuple_windows()
-            .for_each(|(point_1, point_2)| {
-                ui.painter().add(Shape::convex_polygon(
-                    vec![center, *point_1, *point_2],
-                    fill,
-                    Stroke::new(1.0, fill),
-                ));
-            });
-
-        ui.painter().add(Shape::closed_line(outline_points, stroke));
+        ui.painter()
+            .add(concave_polygon(ui.painter(), outline_points, fill, stroke));
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -193,11 +184,7 @@ impl WidgetShape {
         stroke: Stroke,
         rotation: Rot2,
     ) {
-        // NOTE: convex_polygon() is broken, spews rendering artifacts all over
-        //   the window when it tries to render degenerate polygons:
-        //     ∃(P1,P2) ∈ Poly (dist(P1,P2) ≈ 0)
-
-        // HACK: convex_polygon() workaround
+        // Zero-width arcs have no area to fill, only their outline is visible
         if almost_equal(start_angle, end_angle, 0.001) {
             let shape_radius = self.eval(start_angle - (rotation * Vec2::RIGHT).angle());
 
@@ -219,39 +206,17 @@ impl WidgetShape {
             })
         };
 
-        // HACK: convex_polygon() workaround
-        let inner_radius = inner_radius.max(0.1);
-
-        let outer_arc = generate_arc_points(outer_radius).collect::<Vec<_>>();
-        let inner_arc = generate_arc_points(inner_radius).collect::<Vec<_>>();
-
-        // https://github.com/emilk/egui/issues/513
-        outer_arc
-            .iter()
-            .zip(inner_arc.iter())
-            .tuple_windows()
-            .for_each(|((outer_1, inner_1), (outer_2, inner_2))| {
-                ui.painter().add(Shape::convex_polygon(
-                    vec![*outer_1, *inner_1, *inner_2, *outer_2],
-                    fill,
-                    Stroke::new(1.0, fill),
-                ));
-            });
-
-        let outline_points: Vec<Pos2> = outer_arc
-            .iter()
-            .chain(inner_arc.iter().rev())
-            .copied()
-            .collect();
-
-        ui.painter().add(Shape::closed_line(outline_points, stroke));
-
-        // TODO: Remove hacks and paint the arc with a single call:
-        // Shape::concave_polygon(
-        //     outline_points, // outer_arc.chain(inner_arc.rev())
-        //     fill,
-        //     stroke,
-        // )
+        let outline_points = generate_arc_points(outer_radius)
+            .chain(
+                generate_arc_points(inner_radius)
+                    .collect_vec()
+                    .into_iter()
+                    .rev(),
+            )
+            .collect_vec();
+
+        ui.painter()
+            .add(concave_polygon(ui.painter(), outline_points, fill, stroke));
     }
 }
 
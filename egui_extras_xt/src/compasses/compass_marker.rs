@@ -7,6 +7,7 @@ use strum::Display;
 
 use crate::common::normalized_angle_unsigned_excl;
 use crate::hash::PearsonHash;
+use crate::polygon::concave_polygon;
 
 // ----------------------------------------------------------------------------
 
@@ -234,9 +235,8 @@ impl CompassMarkerShape {
                         ) * inner_radius
                 });
 
-                // TODO: Broken polygon renderer
-                // https://github.com/emilk/egui/issues/513
-                ui.painter().add(Shape::convex_polygon(
+                ui.painter().add(concave_polygon(
+                    ui.painter(),
                     outer_points.interleave(inner_points).collect_vec(),
                     fill,
                     stroke,
@@ -1,7 +1,7 @@
 use std::f32::consts::TAU;
 
 use ecolor::tint_color_towards;
-use egui::{self, Response, Sense, Ui, Widget};
+use egui::{self, Response, Sense, Ui, UiBuilder, Widget};
 use emath::{normalized_angle, pos2, vec2, Align2, Rect, Vec2};
 use epaint::{Color32, FontFamily, FontId, Stroke};
 
@@ -191,7 +191,7 @@ impl<'a> Widget for LinearCompass<'a> {
             },
         );
 
-        let mut child_ui = ui.child_ui(rect, *ui.layout(), None);
+        let mut child_ui = ui.new_child(UiBuilder::new().max_rect(rect).layout(*ui.layout()));
         child_ui.set_clip_rect(child_ui.clip_rect().intersect(rect));
 
         let constrain_value = |mut value| {
@@ -0,0 +1,343 @@
+use std::ops::RangeInclusive;
+
+use egui::{self, remap_clamp, vec2, Color32, Rect, Response, Sense, Ui, Widget};
+use emath::Vec2;
+use epaint::Stroke;
+use strum::{Display, EnumIter};
+
+use crate::displays::peak_hold::PeakHold;
+use crate::displays::{DisplayStyle, DisplayStylePreset, ThemedDisplayStyle};
+
+// ----------------------------------------------------------------------------
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Display, EnumIter, Eq, PartialEq)]
+pub enum BarGraphOrientation {
+    /// Fills from bottom to top.
+    #[strum(to_string = "Vertical")]
+    Vertical,
+
+    /// Fills from left to right.
+    #[strum(to_string = "Horizontal")]
+    Horizontal,
+}
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Display, EnumIter, Eq, PartialEq)]
+pub enum BarGraphScale {
+    /// Value and range are in the same unit.
+    #[strum(to_string = "Linear")]
+    Linear,
+
+    /// Value is a linear amplitude, range is in decibels relative to `1.0`.
+    #[strum(to_string = "Decibel")]
+    Decibel,
+}
+
+impl BarGraphScale {
+    fn apply(&self, value: f32) -> f32 {
+        match *self {
+            BarGraphScale::Linear => value,
+            BarGraphScale::Decibel => 20.0 * value.abs().max(1e-10).log10(),
+        }
+    }
+}
+
+/// Segments at or above `start` (in range units) are lit in `color`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BarGraphZone {
+    pub start: f32,
+    pub color: Color32,
+}
+
+impl BarGraphZone {
+    pub const GREEN: Color32 = Color32::from_rgb(0x00, 0xF0, 0x00);
+    pub const YELLOW: Color32 = Color32::from_rgb(0xF0, 0xD0, 0x00);
+    pub const RED: Color32 = Color32::from_rgb(0xF0, 0x00, 0x00);
+
+    pub fn new(start: f32, color: Color32) -> Self {
+        Self { start, color }
+    }
+
+    /// Green, yellow and red zones of a typical audio level meter.
+    #[must_use]
+    pub fn traffic_light(yellow_start: f32, red_start: f32) -> Vec<Self> {
+        vec![
+            Self::new(f32::NEG_INFINITY, Self::GREEN),
+            Self::new(yellow_start, Self::YELLOW),
+            Self::new(red_start, Self::RED),
+        ]
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Segmented level meter, like the VU and PPM meters of audio equipment.
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct BarGraphDisplay {
+    value: f32,
+    range: RangeInclusive<f32>,
+    scale: BarGraphScale,
+    orientation: BarGraphOrientation,
+    segment_count: usize,
+    segment_spacing: f32,
+    length: f32,
+    thickness: f32,
+    zones: Vec<BarGraphZone>,
+    peak_hold: bool,
+    peak_hold_time: f32,
+    peak_decay_rate: f32,
+    style: ThemedDisplayStyle,
+    animated: bool,
+}
+
+impl BarGraphDisplay {
+    pub fn new(value: f32) -> Self {
+        Self {
+            value,
+            range: 0.0..=1.0,
+            scale: BarGraphScale::Linear,
+            orientation: BarGraphOrientation::Vertical,
+            segment_count: 20,
+            segment_spacing: 0.25,
+            length: 160.0,
+            thickness: 16.0,
+            zones: Vec::new(),
+            peak_hold: false,
+            peak_hold_time: 1.5,
+            peak_decay_rate: 0.5,
+            style: ThemedDisplayStyle::default(),
+            animated: true,
+        }
+    }
+
+    /// Audio level meter for linear sample amplitudes, from -60 dBFS to
+    /// 0 dBFS with the usual green, yellow and red zones.
+    pub fn decibel(amplitude: f32) -> Self {
+        Self::new(amplitude)
+            .scale(BarGraphScale::Decibel)
+            .range(-60.0..=0.0)
+            .zones(BarGraphZone::traffic_light(-12.0, -3.0))
+            .peak_hold(true)
+    }
+
+    pub fn range(mut self, range: RangeInclusive<f32>) -> Self {
+        self.range = range;
+        self
+    }
+
+    pub fn scale(mut self, scale: BarGraphScale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn orientation(mut self, orientation: BarGraphOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    pub fn segment_count(mut self, segment_count: usize) -> Self {
+        self.segment_count = segment_count.max(1);
+        self
+    }
+
+    /// Gap between segments, relative to the segment pitch.
+    pub fn segment_spacing(mut self, segment_spacing: impl Into<f32>) -> Self {
+        self.segment_spacing = segment_spacing.into();
+        self
+    }
+
+    pub fn length(mut self, length: impl Into<f32>) -> Self {
+        self.length = length.into();
+        self
+    }
+
+    pub fn thickness(mut self, thickness: impl Into<f32>) -> Self {
+        self.thickness = thickness.into();
+        self
+    }
+
+    /// Colored zones, in range units. Segments below the first zone use the
+    /// style's active foreground color.
+    pub fn zones(mut self, zones: Vec<BarGraphZone>) -> Self {
+        self.zones = zones;
+        self.zones.sort_by(|a, b| a.start.total_cmp(&b.start));
+        self
+    }
+
+    pub fn peak_hold(mut self, peak_hold: bool) -> Self {
+        self.peak_hold = peak_hold;
+        self
+    }
+
+    /// Time the peak indicator stays put before decaying, in seconds.
+    pub fn peak_hold_time(mut self, peak_hold_time: impl Into<f32>) -> Self {
+        self.peak_hold_time = peak_hold_time.into();
+        self
+    }
+
+    /// Fall speed of the peak indicator, in full scales per second.
+    pub fn peak_decay_rate(mut self, peak_decay_rate: impl Into<f32>) -> Self {
+        self.peak_decay_rate = peak_decay_rate.into();
+        self
+    }
+
+    pub fn style(mut self, style: DisplayStyle) -> Self {
+        self.style = ThemedDisplayStyle::Fixed(style);
+        self
+    }
+
+    pub fn style_preset(mut self, preset: DisplayStylePreset) -> Self {
+        self.style = ThemedDisplayStyle::Preset(preset);
+        self
+    }
+
+    /// Uses the style registered under `name` in the
+    /// [`DisplayStyleRegistry`](crate::displays::DisplayStyleRegistry).
+    pub fn style_name(mut self, name: impl Into<String>) -> Self {
+        self.style = ThemedDisplayStyle::Named(name.into());
+        self
+    }
+
+    pub fn animated(mut self, animated: bool) -> Self {
+        self.animated = animated;
+        self
+    }
+
+    fn desired_size(&self) -> Vec2 {
+        match self.orientation {
+            BarGraphOrientation::Vertical => vec2(self.thickness, self.length),
+            BarGraphOrientation::Horizontal => vec2(self.length, self.thickness),
+        }
+    }
+
+    fn segment_color(&self, style: &DisplayStyle, segment_index: usize) -> Color32 {
+        let segment_value = egui::lerp(
+            self.range.clone(),
+            segment_index as f32 / self.segment_count as f32,
+        );
+
+        self.zones
+            .iter()
+            .rev()
+            .find(|zone| segment_value >= zone.start)
+            .map_or(style.active_foreground_color, |zone| zone.color)
+    }
+
+    fn segment_rect(&self, rect: Rect, segment_index: usize) -> Rect {
+        let margin = self.thickness * 0.15;
+        let inner_rect = rect.shrink(margin);
+
+        let pitch = match self.orientation {
+            BarGraphOrientation::Vertical => inner_rect.height(),
+            BarGraphOrientation::Horizontal => inner_rect.width(),
+        } / self.segment_count as f32;
+        let gap = pitch * self.segment_spacing;
+
+        let start = segment_index as f32 * pitch + gap / 2.0;
+        let end = (segment_index + 1) as f32 * pitch - gap / 2.0;
+
+        match self.orientation {
+            BarGraphOrientation::Vertical => Rect::from_x_y_ranges(
+                inner_rect.x_range(),
+                (inner_rect.bottom() - end)..=(inner_rect.bottom() - start),
+            ),
+            BarGraphOrientation::Horizontal => Rect::from_x_y_ranges(
+                (inner_rect.left() + start)..=(inner_rect.left() + end),
+                inner_rect.y_range(),
+            ),
+        }
+    }
+
+    /// Peak level after hold and decay, tracked in memory.
+    fn update_peak(&self, ui: &Ui, id: egui::Id, level: f32) -> f32 {
+        let time = ui.input(|input| input.time);
+
+        let mut state = ui
+            .memory(|memory| memory.data.get_temp::<PeakHold>(id))
+            .unwrap_or(PeakHold::new(level, time));
+
+        let peak = state.update(level, time, self.peak_hold_time, self.peak_decay_rate);
+        ui.memory_mut(|memory| memory.data.insert_temp(id, state));
+
+        // Without decay the peak stays put, nothing to animate
+        if (peak > level) && (self.peak_decay_rate > 0.0) {
+            ui.ctx().request_repaint();
+        }
+
+        peak
+    }
+}
+
+impl Widget for BarGraphDisplay {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let (rect, response) = ui.allocate_exact_size(self.desired_size(), Sense::hover());
+
+        let level = remap_clamp(self.scale.apply(self.value), self.range.clone(), 0.0..=1.0);
+        let level = if self.animated {
+            ui.ctx().animate_value_with_time(response.id, level, 0.1)
+        } else {
+            level
+        };
+
+        let peak = self
+            .peak_hold
+            .then(|| self.update_peak(ui, response.id, level));
+
+        if ui.is_rect_visible(rect) {
+            let style = self.style.resolve(ui);
+
+            ui.painter().rect(
+                rect,
+                ui.style().visuals.noninteractive().rounding,
+                style.background_color,
+                Stroke::NONE,
+            );
+
+            let lit_segments = level * self.segment_count as f32;
+            let peak_segment = peak
+                .filter(|&peak| peak > 0.0)
+                .map(|peak| ((peak * self.segment_count as f32).ceil() as usize).max(1) - 1);
+
+            for segment_index in 0..self.segment_count {
+                let segment_level = if Some(segment_index) == peak_segment {
+                    1.0
+                } else {
+                    (lit_segments - segment_index as f32).clamp(0.0, 1.0)
+                };
+
+                let segment_style = DisplayStyle {
+                    active_foreground_color: self.segment_color(&style, segment_index),
+                    ..style
+                };
+                let segment_rect = self.segment_rect(rect, segment_index);
+
+                segment_style.paint_glow(
+                    ui.painter(),
+                    &[
+                        segment_rect.left_top(),
+                        segment_rect.right_top(),
+                        segment_rect.right_bottom(),
+                        segment_rect.left_bottom(),
+                    ],
+                    segment_level,
+                );
+
+                ui.painter().rect(
+                    segment_rect,
+                    1.0,
+                    segment_style.foreground_color_blend(segment_level),
+                    segment_style.foreground_stroke_blend(segment_level),
+                );
+            }
+
+            style.paint_reflection(
+                ui.painter(),
+                rect,
+                ui.style().visuals.noninteractive().rounding,
+            );
+        }
+
+        response
+    }
+}
@@ -0,0 +1,46 @@
+mod rom_font;
+mod widget;
+
+use strum::{Display, EnumIter};
+
+pub use rom_font::rom_pattern;
+pub use widget::CharacterDisplayWidget;
+
+// ----------------------------------------------------------------------------
+
+/// 5x8 dot pattern, one byte per row from top to bottom, bit 4 is the leftmost
+/// column. Same layout as HD44780 CGRAM data.
+pub type CharacterPattern = [u8; 8];
+
+/// Number of user-definable characters, addressed by `'\u{0}'..='\u{7}'`.
+pub const CGRAM_SIZE: usize = 8;
+
+// ----------------------------------------------------------------------------
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Display, EnumIter, Eq, PartialEq)]
+pub enum CursorMode {
+    #[strum(to_string = "Hidden")]
+    Hidden,
+
+    #[strum(to_string = "Underline")]
+    Underline,
+
+    #[strum(to_string = "Blink")]
+    Blink,
+
+    #[strum(to_string = "Underline + Blink")]
+    UnderlineBlink,
+}
+
+impl CursorMode {
+    #[must_use]
+    pub fn underline(&self) -> bool {
+        matches!(*self, CursorMode::Underline | CursorMode::UnderlineBlink)
+    }
+
+    #[must_use]
+    pub fn blink(&self) -> bool {
+        matches!(*self, CursorMode::Blink | CursorMode::UnderlineBlink)
+    }
+}
@@ -0,0 +1,205 @@
+use std::time::Duration;
+
+use egui::{vec2, Rect, Response, Sense, Stroke, Ui, Vec2, Widget};
+
+use crate::displays::character_display::{rom_pattern, CharacterPattern, CursorMode, CGRAM_SIZE};
+use crate::displays::{DisplayStyle, DisplayStylePreset, ThemedDisplayStyle};
+
+// HD44780 blinks at roughly 1.2 Hz with the typical 270 kHz oscillator.
+const BLINK_HALF_PERIOD: f64 = 0.4096;
+
+const CELL_COLUMNS: usize = 5;
+const CELL_ROWS: usize = 8;
+
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct CharacterDisplayWidget {
+    columns: usize,
+    rows: usize,
+    text: String,
+    cgram: [CharacterPattern; CGRAM_SIZE],
+    cursor_position: Option<(usize, usize)>,
+    cursor_mode: CursorMode,
+    dot_size: f32,
+    dot_spacing: f32,
+    style: ThemedDisplayStyle,
+}
+
+impl CharacterDisplayWidget {
+    pub fn new(columns: usize, rows: usize) -> Self {
+        Self {
+            columns,
+            rows,
+            text: String::new(),
+            cgram: [[0x00; CELL_ROWS]; CGRAM_SIZE],
+            cursor_position: None,
+            cursor_mode: CursorMode::Hidden,
+            dot_size: 4.0,
+            dot_spacing: 0.15,
+            style: ThemedDisplayStyle::default(),
+        }
+    }
+
+    /// Lines are separated by `'\n'`, overflowing text is cut off.
+    /// Characters `'\u{0}'..='\u{7}'` display the CGRAM patterns.
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Sets a custom character pattern. The index wraps around like the CGRAM
+    /// address of the HD44780.
+    pub fn cgram(mut self, index: usize, pattern: CharacterPattern) -> Self {
+        self.cgram[index % CGRAM_SIZE] = pattern;
+        self
+    }
+
+    pub fn cursor_position(mut self, column: usize, row: usize) -> Self {
+        self.cursor_position = Some((column, row));
+        self
+    }
+
+    pub fn cursor_mode(mut self, cursor_mode: CursorMode) -> Self {
+        self.cursor_mode = cursor_mode;
+        self
+    }
+
+    pub fn dot_size(mut self, dot_size: impl Into<f32>) -> Self {
+        self.dot_size = dot_size.into();
+        self
+    }
+
+    /// Gap between dots, relative to the dot size.
+    pub fn dot_spacing(mut self, dot_spacing: impl Into<f32>) -> Self {
+        self.dot_spacing = dot_spacing.into();
+        self
+    }
+
+    pub fn style(mut self, style: DisplayStyle) -> Self {
+        self.style = ThemedDisplayStyle::Fixed(style);
+        self
+    }
+
+    pub fn style_preset(mut self, preset: DisplayStylePreset) -> Self {
+        self.style = ThemedDisplayStyle::Preset(preset);
+        self
+    }
+
+    /// Uses the style registered under `name` in the
+    /// [`DisplayStyleRegistry`](crate::displays::DisplayStyleRegistry).
+    pub fn style_name(mut self, name: impl Into<String>) -> Self {
+        self.style = ThemedDisplayStyle::Named(name.into());
+        self
+    }
+
+    fn pattern(&self, c: char) -> CharacterPattern {
+        match c as usize {
+            index if index < CGRAM_SIZE => self.cgram[index],
+            _ => rom_pattern(c).unwrap_or_default(),
+        }
+    }
+}
+
+impl Widget for CharacterDisplayWidget {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let dot_pitch = self.dot_size * (1.0 + self.dot_spacing);
+        let cell_size = vec2(CELL_COLUMNS as f32, CELL_ROWS as f32) * dot_pitch;
+        let cell_pitch = cell_size + Vec2::splat(dot_pitch);
+        let margin = Vec2::splat(dot_pitch * 2.0);
+
+        let desired_size = vec2(
+            cell_pitch.x * self.columns as f32 - dot_pitch,
+            cell_pitch.y * self.rows as f32 - dot_pitch,
+        ) + 2.0 * margin;
+
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        if ui.is_rect_visible(rect) {
+            let style = self.style.resolve(ui);
+
+            ui.painter().rect(
+                rect,
+                ui.style().visuals.noninteractive().rounding,
+                style.background_color,
+                Stroke::NONE,
+            );
+
+            let blink_phase = if self.cursor_position.is_some() && self.cursor_mode.blink() {
+                let time = ui.input(|input| input.time);
+                ui.ctx().request_repaint_after(Duration::from_secs_f64(
+                    BLINK_HALF_PERIOD - (time % BLINK_HALF_PERIOD),
+                ));
+                ((time / BLINK_HALF_PERIOD) as u64) % 2 == 0
+            } else {
+                false
+            };
+
+            let lines = self.text.lines().collect::<Vec<_>>();
+
+            for row in 0..self.rows {
+                let mut line_chars = lines.get(row).map(|line| line.chars());
+
+                for column in 0..self.columns {
+                    let c = line_chars.as_mut().and_then(Iterator::next).unwrap_or(' ');
+
+                    let mut pattern = self.pattern(c);
+
+                    if self.cursor_position == Some((column, row)) {
+                        if self.cursor_mode.underline() {
+                            pattern[CELL_ROWS - 1] = 0x1F;
+                        }
+                        if blink_phase {
+                            pattern = [0x1F; CELL_ROWS];
+                        }
+                    }
+
+                    let cell_origin = rect.min
+                        + margin
+                        + vec2(column as f32 * cell_pitch.x, row as f32 * cell_pitch.y);
+
+                    for (dot_row, row_bits) in pattern.iter().enumerate() {
+                        for dot_column in 0..CELL_COLUMNS {
+                            let dot_active =
+                                (row_bits >> (CELL_COLUMNS - 1 - dot_column)) & 0x01 != 0x00;
+
+                            let dot_rect = Rect::from_min_size(
+                                cell_origin + vec2(dot_column as f32, dot_row as f32) * dot_pitch,
+                                Vec2::splat(self.dot_size),
+                            );
+
+                            ui.painter().rect(
+                                dot_rect,
+                                0.0,
+                                style.foreground_color(dot_active),
+                                style.foreground_stroke(dot_active),
+                            );
+                        }
+                    }
+                }
+            }
+
+            style.paint_reflection(
+                ui.painter(),
+                rect,
+                ui.style().visuals.noninteractive().rounding,
+            );
+        }
+
+        response
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cgram_index_wraps() {
+        let pattern = [0x1F, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1F, 0x00];
+        let widget = CharacterDisplayWidget::new(16, 2).cgram(CGRAM_SIZE + 3, pattern);
+
+        assert_eq!(widget.pattern('\u{3}'), pattern);
+        assert_eq!(widget.pattern('\u{0}'), [0x00; CELL_ROWS]);
+    }
+}
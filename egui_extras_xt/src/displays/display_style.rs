@@ -1,8 +1,76 @@
-use egui::{lerp, Color32, Rgba, Stroke, Ui};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use egui::{
+    lerp, vec2, Color32, Context, Id, Mesh, Painter, Pos2, Rect, Rgba, Rounding, Shape, Stroke,
+    Theme, Ui,
+};
 use strum::{Display, EnumIter};
 
 // ----------------------------------------------------------------------------
 
+/// Optional lighting effects, painted with layered translucent shapes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DisplayEffects {
+    /// Width of the halo around lit segments, in points. `0.0` disables it.
+    pub glow_radius: f32,
+
+    /// Opacity of the halo next to the lit segments.
+    pub glow_intensity: f32,
+
+    /// Fade-out time of segments turning off, in seconds.
+    pub persistence: f32,
+
+    /// Opacity of the glass reflection over the upper half of the display.
+    pub reflection: f32,
+}
+
+impl DisplayEffects {
+    pub const NONE: Self = Self {
+        glow_radius: 0.0,
+        glow_intensity: 0.0,
+        persistence: 0.0,
+        reflection: 0.0,
+    };
+
+    /// Vacuum fluorescent displays: strong glow, short phosphor afterglow.
+    pub const VFD: Self = Self {
+        glow_radius: 6.0,
+        glow_intensity: 0.35,
+        persistence: 0.15,
+        reflection: 0.06,
+    };
+
+    /// LED displays: faint glow, no afterglow.
+    pub const LED: Self = Self {
+        glow_radius: 4.0,
+        glow_intensity: 0.25,
+        persistence: 0.0,
+        reflection: 0.0,
+    };
+
+    /// Liquid crystal displays: slow pixel response behind glass.
+    pub const LCD: Self = Self {
+        glow_radius: 0.0,
+        glow_intensity: 0.0,
+        persistence: 0.08,
+        reflection: 0.12,
+    };
+
+    #[must_use]
+    pub fn has_glow(&self) -> bool {
+        (self.glow_radius > 0.0) && (self.glow_intensity > 0.0)
+    }
+}
+
+impl Default for DisplayEffects {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 #[derive(Clone, Copy, Debug)]
 pub struct DisplayStyle {
     pub background_color: Color32,
@@ -10,6 +78,7 @@ pub struct DisplayStyle {
     pub active_foreground_stroke: Stroke,
     pub inactive_foreground_color: Color32,
     pub inactive_foreground_stroke: Stroke,
+    pub effects: DisplayEffects,
 }
 
 impl DisplayStyle {
@@ -54,6 +123,78 @@ impl DisplayStyle {
         )
     }
 
+    const GLOW_LAYERS: usize = 4;
+
+    fn glow_layers(&self, intensity: f32) -> impl Iterator<Item = Stroke> + '_ {
+        let layer_color = self
+            .active_foreground_color
+            .gamma_multiply(self.effects.glow_intensity * intensity / Self::GLOW_LAYERS as f32);
+
+        (1..=Self::GLOW_LAYERS).map(move |layer| {
+            Stroke::new(
+                2.0 * self.effects.glow_radius * (layer as f32 / Self::GLOW_LAYERS as f32),
+                layer_color,
+            )
+        })
+    }
+
+    /// Halo around a lit outline, painted before the outline itself.
+    pub(crate) fn paint_glow(&self, painter: &Painter, points: &[Pos2], intensity: f32) {
+        if !self.effects.has_glow() || (intensity <= 0.0) {
+            return;
+        }
+
+        for stroke in self.glow_layers(intensity) {
+            painter.add(Shape::closed_line(points.to_vec(), stroke));
+        }
+    }
+
+    pub(crate) fn paint_glow_circle(
+        &self,
+        painter: &Painter,
+        center: Pos2,
+        radius: f32,
+        intensity: f32,
+    ) {
+        if !self.effects.has_glow() || (intensity <= 0.0) {
+            return;
+        }
+
+        for stroke in self.glow_layers(intensity) {
+            painter.circle_stroke(center, radius, stroke);
+        }
+    }
+
+    /// Glass reflection fading out towards the middle of `rect`.
+    pub(crate) fn paint_reflection(&self, painter: &Painter, rect: Rect, rounding: Rounding) {
+        if self.effects.reflection <= 0.0 {
+            return;
+        }
+
+        let rect = Rect::from_min_max(
+            rect.left_top() + vec2(rounding.nw, 0.0),
+            rect.right_center() - vec2(rounding.ne, 0.0),
+        );
+        let reflection_color = Color32::WHITE.gamma_multiply(self.effects.reflection);
+
+        let mut mesh = Mesh::default();
+        mesh.colored_vertex(rect.left_top(), reflection_color);
+        mesh.colored_vertex(rect.right_top(), reflection_color);
+        mesh.colored_vertex(rect.right_bottom(), Color32::TRANSPARENT);
+        mesh.colored_vertex(rect.left_bottom(), Color32::TRANSPARENT);
+        mesh.add_triangle(0, 1, 2);
+        mesh.add_triangle(0, 2, 3);
+
+        painter.add(mesh);
+    }
+
+    /// Same style with lighting effects, e.g. `preset.style().effects(preset.effects())`.
+    #[must_use]
+    pub fn effects(mut self, effects: DisplayEffects) -> Self {
+        self.effects = effects;
+        self
+    }
+
     #[must_use]
     pub fn system_style(ui: &Ui) -> Self {
         DisplayStyle {
@@ -62,10 +203,49 @@ impl DisplayStyle {
             active_foreground_stroke: Stroke::NONE,
             inactive_foreground_color: ui.style().visuals.faint_bg_color,
             inactive_foreground_stroke: Stroke::NONE,
+            effects: DisplayEffects::NONE,
+        }
+    }
+
+    /// Counterpart of an emissive style for light backgrounds: pale panel,
+    /// darker segments, weaker glow.
+    #[must_use]
+    pub fn to_light(&self) -> Self {
+        let background_color = mix_colors(self.background_color, Color32::WHITE, 0.85);
+        let active_foreground_color = mix_colors(self.active_foreground_color, Color32::BLACK, 0.3);
+
+        DisplayStyle {
+            background_color,
+            active_foreground_color,
+            inactive_foreground_color: mix_colors(background_color, active_foreground_color, 0.12),
+            effects: DisplayEffects {
+                glow_intensity: self.effects.glow_intensity * 0.5,
+                ..self.effects
+            },
+            ..*self
+        }
+    }
+
+    /// Counterpart of a reflective style for dark backgrounds, like a dimmed
+    /// backlight.
+    #[must_use]
+    pub fn to_dark(&self) -> Self {
+        DisplayStyle {
+            background_color: mix_colors(self.background_color, Color32::BLACK, 0.55),
+            inactive_foreground_color: mix_colors(
+                self.inactive_foreground_color,
+                Color32::BLACK,
+                0.55,
+            ),
+            ..*self
         }
     }
 }
 
+fn mix_colors(a: Color32, b: Color32, t: f32) -> Color32 {
+    Color32::from(lerp(Rgba::from(a)..=Rgba::from(b), t))
+}
+
 impl Default for DisplayStyle {
     fn default() -> Self {
         DisplayStylePreset::Default.style()
@@ -115,6 +295,65 @@ pub enum DisplayStylePreset {
 }
 
 impl DisplayStylePreset {
+    /// Theme the preset was designed for, the other variant is derived.
+    #[must_use]
+    pub fn native_theme(&self) -> Theme {
+        match *self {
+            DisplayStylePreset::Calculator
+            | DisplayStylePreset::NintendoGameBoy
+            | DisplayStylePreset::LightBlue
+            | DisplayStylePreset::YamahaMU2000 => Theme::Light,
+            _ => Theme::Dark,
+        }
+    }
+
+    #[must_use]
+    pub fn variants(&self) -> DisplayStyleVariants {
+        let style = self.style();
+
+        match self.native_theme() {
+            Theme::Dark => DisplayStyleVariants {
+                light: style.to_light(),
+                dark: style,
+            },
+            Theme::Light => DisplayStyleVariants {
+                light: style,
+                dark: style.to_dark(),
+            },
+        }
+    }
+
+    #[must_use]
+    pub fn style_for_theme(&self, theme: Theme) -> DisplayStyle {
+        self.variants().for_theme(theme)
+    }
+
+    /// Style matching `ui.visuals().dark_mode`.
+    #[must_use]
+    pub fn themed_style(&self, ui: &Ui) -> DisplayStyle {
+        self.style_for_theme(Theme::from_dark_mode(ui.visuals().dark_mode))
+    }
+
+    /// Effects matching the display technology of the preset. They are off
+    /// in [`Self::style`], turn them on with [`DisplayStyle::effects`].
+    #[must_use]
+    pub fn effects(&self) -> DisplayEffects {
+        match *self {
+            DisplayStylePreset::Calculator
+            | DisplayStylePreset::NintendoGameBoy
+            | DisplayStylePreset::BlueNegative
+            | DisplayStylePreset::LightBlue
+            | DisplayStylePreset::YamahaMU2000 => DisplayEffects::LCD,
+            DisplayStylePreset::KnightRider => DisplayEffects::LED,
+            DisplayStylePreset::Amber
+            | DisplayStylePreset::DeLoreanRed
+            | DisplayStylePreset::DeLoreanGreen
+            | DisplayStylePreset::DeLoreanAmber => DisplayEffects::VFD,
+            _ => DisplayEffects::NONE,
+        }
+    }
+
+    /// Style as originally designed, regardless of the current theme.
     #[must_use]
     pub fn style(&self) -> DisplayStyle {
         match *self {
@@ -124,6 +363,7 @@ impl DisplayStylePreset {
                 active_foreground_stroke: Stroke::NONE,
                 inactive_foreground_color: Color32::from_rgb(0x00, 0x30, 0x00),
                 inactive_foreground_stroke: Stroke::NONE,
+                effects: DisplayEffects::NONE,
             },
             DisplayStylePreset::Calculator => DisplayStyle {
                 background_color: Color32::from_rgb(0xC5, 0xCB, 0xB6),
@@ -131,6 +371,7 @@ impl DisplayStylePreset {
                 active_foreground_stroke: Stroke::NONE,
                 inactive_foreground_color: Color32::from_rgb(0xB9, 0xBE, 0xAB),
                 inactive_foreground_stroke: Stroke::NONE,
+                effects: DisplayEffects::NONE,
             },
             DisplayStylePreset::NintendoGameBoy => DisplayStyle {
                 background_color: Color32::from_rgb(0x9B, 0xBC, 0x0F),
@@ -138,6 +379,7 @@ impl DisplayStylePreset {
                 active_foreground_stroke: Stroke::NONE,
                 inactive_foreground_color: Color32::from_rgb(0x8B, 0xAC, 0x0F),
                 inactive_foreground_stroke: Stroke::NONE,
+                effects: DisplayEffects::NONE,
             },
             DisplayStylePreset::KnightRider => DisplayStyle {
                 background_color: Color32::from_rgb(0x10, 0x00, 0x00),
@@ -145,6 +387,7 @@ impl DisplayStylePreset {
                 active_foreground_stroke: Stroke::NONE,
                 inactive_foreground_color: Color32::from_rgb(0x20, 0x00, 0x00),
                 inactive_foreground_stroke: Stroke::NONE,
+                effects: DisplayEffects::NONE,
             },
             DisplayStylePreset::BlueNegative => DisplayStyle {
                 background_color: Color32::from_rgb(0x00, 0x00, 0xFF),
@@ -152,6 +395,7 @@ impl DisplayStylePreset {
                 active_foreground_stroke: Stroke::NONE,
                 inactive_foreground_color: Color32::from_rgb(0x28, 0x28, 0xFF),
                 inactive_foreground_stroke: Stroke::NONE,
+                effects: DisplayEffects::NONE,
             },
             DisplayStylePreset::Amber => DisplayStyle {
                 background_color: Color32::from_rgb(0x1D, 0x12, 0x07),
@@ -159,6 +403,7 @@ impl DisplayStylePreset {
                 active_foreground_stroke: Stroke::NONE,
                 inactive_foreground_color: Color32::from_rgb(0x33, 0x20, 0x00),
                 inactive_foreground_stroke: Stroke::NONE,
+                effects: DisplayEffects::NONE,
             },
             DisplayStylePreset::LightBlue => DisplayStyle {
                 background_color: Color32::from_rgb(0x0F, 0xB0, 0xBC),
@@ -166,6 +411,7 @@ impl DisplayStylePreset {
                 active_foreground_stroke: Stroke::NONE,
                 inactive_foreground_color: Color32::from_black_alpha(60),
                 inactive_foreground_stroke: Stroke::NONE,
+                effects: DisplayEffects::NONE,
             },
             DisplayStylePreset::DeLoreanRed => DisplayStyle {
                 background_color: Color32::from_rgb(0x12, 0x07, 0x0A),
@@ -173,6 +419,7 @@ impl DisplayStylePreset {
                 active_foreground_stroke: Stroke::NONE,
                 inactive_foreground_color: Color32::from_rgb(0x48, 0x0A, 0x0B),
                 inactive_foreground_stroke: Stroke::NONE,
+                effects: DisplayEffects::NONE,
             },
             DisplayStylePreset::DeLoreanGreen => DisplayStyle {
                 background_color: Color32::from_rgb(0x05, 0x0A, 0x0A),
@@ -180,6 +427,7 @@ impl DisplayStylePreset {
                 active_foreground_stroke: Stroke::NONE,
                 inactive_foreground_color: Color32::from_rgb(0x07, 0x29, 0x0F),
                 inactive_foreground_stroke: Stroke::NONE,
+                effects: DisplayEffects::NONE,
             },
             DisplayStylePreset::DeLoreanAmber => DisplayStyle {
                 background_color: Color32::from_rgb(0x08, 0x08, 0x0B),
@@ -187,6 +435,7 @@ impl DisplayStylePreset {
                 active_foreground_stroke: Stroke::NONE,
                 inactive_foreground_color: Color32::from_rgb(0x51, 0x2C, 0x0F),
                 inactive_foreground_stroke: Stroke::NONE,
+                effects: DisplayEffects::NONE,
             },
             DisplayStylePreset::YamahaMU2000 => DisplayStyle {
                 background_color: Color32::from_rgb(0x8C, 0xD7, 0x01),
@@ -194,6 +443,7 @@ impl DisplayStylePreset {
                 active_foreground_stroke: Stroke::NONE,
                 inactive_foreground_color: Color32::from_rgb(0x7B, 0xCE, 0x02),
                 inactive_foreground_stroke: Stroke::NONE,
+                effects: DisplayEffects::NONE,
             },
             DisplayStylePreset::Dracula => DisplayStyle {
                 background_color: Color32::from_rgb(0x26, 0x12, 0x1E), // Dracula pink, HSV(.., .., 15%)
@@ -201,7 +451,160 @@ impl DisplayStylePreset {
                 active_foreground_stroke: Stroke::NONE,
                 inactive_foreground_color: Color32::from_rgb(0x41, 0x1F, 0x33), // Dracula pink, HSV(.., .., 25%)
                 inactive_foreground_stroke: Stroke::NONE,
+                effects: DisplayEffects::NONE,
             },
         }
     }
 }
+
+// ----------------------------------------------------------------------------
+
+/// Light and dark variants of the same display style.
+#[derive(Clone, Copy, Debug)]
+pub struct DisplayStyleVariants {
+    pub light: DisplayStyle,
+    pub dark: DisplayStyle,
+}
+
+impl DisplayStyleVariants {
+    /// Same style in both themes.
+    #[must_use]
+    pub fn uniform(style: DisplayStyle) -> Self {
+        Self {
+            light: style,
+            dark: style,
+        }
+    }
+
+    #[must_use]
+    pub fn for_theme(&self, theme: Theme) -> DisplayStyle {
+        match theme {
+            Theme::Dark => self.dark,
+            Theme::Light => self.light,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Application-wide named display styles, stored in egui memory.
+///
+/// Widgets using [`ThemedDisplayStyle::Named`] pick up changes on the next
+/// frame, so every display in an app can be restyled at once.
+pub struct DisplayStyleRegistry;
+
+type DisplayStyleMap = Arc<BTreeMap<String, DisplayStyleVariants>>;
+
+impl DisplayStyleRegistry {
+    fn id() -> Id {
+        Id::new("egui_extras_xt::displays::DisplayStyleRegistry")
+    }
+
+    fn styles(ctx: &Context) -> DisplayStyleMap {
+        ctx.data(|data| data.get_temp::<DisplayStyleMap>(Self::id()))
+            .unwrap_or_default()
+    }
+
+    fn update(ctx: &Context, f: impl FnOnce(&mut BTreeMap<String, DisplayStyleVariants>)) {
+        ctx.data_mut(|data| {
+            let styles = data.get_temp_mut_or_default::<DisplayStyleMap>(Self::id());
+            f(Arc::make_mut(styles));
+        });
+    }
+
+    /// Adds or replaces the style registered under `name`.
+    pub fn register(ctx: &Context, name: impl Into<String>, variants: DisplayStyleVariants) {
+        Self::update(ctx, |styles| {
+            styles.insert(name.into(), variants);
+        });
+    }
+
+    pub fn unregister(ctx: &Context, name: &str) {
+        Self::update(ctx, |styles| {
+            styles.remove(name);
+        });
+    }
+
+    #[must_use]
+    pub fn get(ctx: &Context, name: &str) -> Option<DisplayStyleVariants> {
+        Self::styles(ctx).get(name).copied()
+    }
+
+    #[must_use]
+    pub fn names(ctx: &Context) -> Vec<String> {
+        Self::styles(ctx).keys().cloned().collect()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Display style resolved against the current theme when the widget is painted.
+#[derive(Clone, Debug)]
+pub enum ThemedDisplayStyle {
+    /// Same style in both themes.
+    Fixed(DisplayStyle),
+
+    /// Light or dark variant of a built-in preset.
+    Preset(DisplayStylePreset),
+
+    /// Style from the [`DisplayStyleRegistry`], falls back to the default
+    /// preset when nothing is registered under this name.
+    Named(String),
+}
+
+impl ThemedDisplayStyle {
+    #[must_use]
+    pub fn resolve(&self, ui: &Ui) -> DisplayStyle {
+        let theme = Theme::from_dark_mode(ui.visuals().dark_mode);
+
+        match self {
+            ThemedDisplayStyle::Fixed(style) => *style,
+            ThemedDisplayStyle::Preset(preset) => preset.style_for_theme(theme),
+            ThemedDisplayStyle::Named(name) => DisplayStyleRegistry::get(ui.ctx(), name)
+                .unwrap_or_else(|| DisplayStylePreset::Default.variants())
+                .for_theme(theme),
+        }
+    }
+}
+
+impl Default for ThemedDisplayStyle {
+    fn default() -> Self {
+        ThemedDisplayStyle::Preset(DisplayStylePreset::Default)
+    }
+}
+
+impl From<DisplayStyle> for ThemedDisplayStyle {
+    fn from(style: DisplayStyle) -> Self {
+        ThemedDisplayStyle::Fixed(style)
+    }
+}
+
+impl From<DisplayStylePreset> for ThemedDisplayStyle {
+    fn from(preset: DisplayStylePreset) -> Self {
+        ThemedDisplayStyle::Preset(preset)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn presets_have_no_effects() {
+        for preset in DisplayStylePreset::iter() {
+            assert_eq!(preset.style().effects, DisplayEffects::NONE, "{preset}");
+        }
+    }
+
+    #[test]
+    fn effects_are_opt_in() {
+        let preset = DisplayStylePreset::Amber;
+        let style = preset.style().effects(preset.effects());
+
+        assert_eq!(style.effects, DisplayEffects::VFD);
+        assert_eq!(style.background_color, preset.style().background_color);
+    }
+}
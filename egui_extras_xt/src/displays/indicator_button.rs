@@ -1,20 +1,76 @@
-use egui::{vec2, Align2, FontFamily, FontId, Key, Rect, Response, Sense, Stroke, Ui, Widget};
+use std::time::Duration;
+
+use egui::{
+    vec2, Align2, Color32, FontFamily, FontId, Key, Rect, Response, Sense, Stroke, Ui, Widget,
+};
 use strum::{Display, EnumIter};
 
-use crate::displays::{DisplayStyle, DisplayStylePreset};
+use crate::displays::{DisplayStyle, DisplayStylePreset, LedBlinkPattern, ThemedDisplayStyle};
 
 // ----------------------------------------------------------------------------
 
 /// Combined into one function (rather than two) to make it easier
-/// for the borrow checker.
-type GetSetValue<'a> = Box<dyn 'a + FnMut(Option<bool>) -> bool>;
+/// for the borrow checker. The value is an index into the state table, `0`
+/// being off.
+type GetSetValue<'a> = Box<dyn 'a + FnMut(Option<usize>) -> usize>;
 
-fn get(get_set_value: &mut GetSetValue<'_>) -> bool {
+fn get(get_set_value: &mut GetSetValue<'_>) -> usize {
     (get_set_value)(None)
 }
 
-fn set(get_set_value: &mut GetSetValue<'_>, value: bool) {
-    (get_set_value)(Some(value));
+fn set(get_set_value: &mut GetSetValue<'_>, state: usize) {
+    (get_set_value)(Some(state));
+}
+
+/// State a toggle steps to, values past the state table wrap back to `0`.
+fn next_state(state: usize, state_count: usize) -> usize {
+    if state + 1 < state_count.max(2) {
+        state + 1
+    } else {
+        0
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Appearance of the indicator in one state.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct IndicatorState {
+    /// Blend between the inactive and active foreground colors.
+    pub level: f32,
+
+    /// Replaces the style's active foreground color.
+    pub color: Option<Color32>,
+
+    pub blink_pattern: LedBlinkPattern,
+}
+
+impl IndicatorState {
+    pub const OFF: Self = Self {
+        level: 0.0,
+        color: None,
+        blink_pattern: LedBlinkPattern::Steady,
+    };
+
+    pub const ON: Self = Self {
+        level: 1.0,
+        color: None,
+        blink_pattern: LedBlinkPattern::Steady,
+    };
+
+    pub fn new(level: f32) -> Self {
+        Self { level, ..Self::OFF }
+    }
+
+    pub fn color(mut self, color: Color32) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn blink_pattern(mut self, blink_pattern: LedBlinkPattern) -> Self {
+        self.blink_pattern = blink_pattern;
+        self
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -27,6 +83,15 @@ pub enum IndicatorButtonBehavior {
 
     #[strum(to_string = "Hold")]
     Hold,
+
+    /// Turns on when clicked, never off. Meant for a group of buttons bound
+    /// to the same value, see [`IndicatorButton::radio`].
+    #[strum(to_string = "Radio")]
+    Radio,
+
+    /// Turns on when clicked and back off after the pulse duration.
+    #[strum(to_string = "Pulse")]
+    Pulse,
 }
 
 // ----------------------------------------------------------------------------
@@ -37,11 +102,13 @@ pub struct IndicatorButton<'a> {
     width: f32,
     height: f32,
     label: Option<String>,
-    style: DisplayStyle,
+    style: ThemedDisplayStyle,
     animated: bool,
     interactive: bool,
     margin: f32,
     behavior: IndicatorButtonBehavior,
+    pulse_duration: f32,
+    states: Vec<IndicatorState>,
 }
 
 impl<'a> IndicatorButton<'a> {
@@ -62,17 +129,80 @@ impl<'a> IndicatorButton<'a> {
         Self::new(value).behavior(IndicatorButtonBehavior::Hold)
     }
 
-    pub fn from_get_set(get_set_value: impl 'a + FnMut(Option<bool>) -> bool) -> Self {
+    /// Lit while `current_value` equals `selected_value`, clicking selects
+    /// it. Buttons bound to the same value form a mutually exclusive group.
+    pub fn radio<T: 'a + Clone + PartialEq>(current_value: &'a mut T, selected_value: T) -> Self {
+        Self::from_get_set(move |v: Option<bool>| {
+            if v == Some(true) {
+                *current_value = selected_value.clone();
+            }
+            *current_value == selected_value
+        })
+        .behavior(IndicatorButtonBehavior::Radio)
+    }
+
+    pub fn pulse(value: &'a mut bool) -> Self {
+        Self::new(value).behavior(IndicatorButtonBehavior::Pulse)
+    }
+
+    /// Binds to an index into `states`, clicking steps to the next state.
+    pub fn multi_state(value: &'a mut usize, states: Vec<IndicatorState>) -> Self {
+        Self::from_get_set_state(
+            move |v: Option<usize>| {
+                if let Some(v) = v {
+                    *value = v;
+                }
+                *value
+            },
+            states,
+        )
+    }
+
+    /// Binds to an enum or any other value listed in `states`, clicking steps
+    /// to the next one. Unlisted values are shown as off.
+    pub fn enum_state<T: 'a + Clone + PartialEq>(
+        value: &'a mut T,
+        states: Vec<(T, IndicatorState)>,
+    ) -> Self {
+        let (values, states): (Vec<T>, Vec<IndicatorState>) = states.into_iter().unzip();
+
+        Self::from_get_set_state(
+            move |v: Option<usize>| {
+                if let Some(new_value) = v.and_then(|v| values.get(v)) {
+                    *value = new_value.clone();
+                }
+                values
+                    .iter()
+                    .position(|v| v == value)
+                    .unwrap_or(values.len())
+            },
+            states,
+        )
+    }
+
+    pub fn from_get_set(mut get_set_value: impl 'a + FnMut(Option<bool>) -> bool) -> Self {
+        Self::from_get_set_state(
+            move |v: Option<usize>| get_set_value(v.map(|v| v != 0)) as usize,
+            vec![IndicatorState::OFF, IndicatorState::ON],
+        )
+    }
+
+    pub fn from_get_set_state(
+        get_set_value: impl 'a + FnMut(Option<usize>) -> usize,
+        states: Vec<IndicatorState>,
+    ) -> Self {
         Self {
             get_set_value: Box::new(get_set_value),
             width: 64.0,
             height: 40.0,
             label: None,
-            style: DisplayStylePreset::Default.style(),
+            style: ThemedDisplayStyle::default(),
             animated: true,
             interactive: true,
             margin: 0.2,
             behavior: IndicatorButtonBehavior::Toggle,
+            pulse_duration: 0.25,
+            states,
         }
     }
 
@@ -92,12 +222,19 @@ impl<'a> IndicatorButton<'a> {
     }
 
     pub fn style(mut self, style: DisplayStyle) -> Self {
-        self.style = style;
+        self.style = ThemedDisplayStyle::Fixed(style);
         self
     }
 
     pub fn style_preset(mut self, preset: DisplayStylePreset) -> Self {
-        self.style = preset.style();
+        self.style = ThemedDisplayStyle::Preset(preset);
+        self
+    }
+
+    /// Uses the style registered under `name` in the
+    /// [`DisplayStyleRegistry`](crate::displays::DisplayStyleRegistry).
+    pub fn style_name(mut self, name: impl Into<String>) -> Self {
+        self.style = ThemedDisplayStyle::Named(name.into());
         self
     }
 
@@ -120,6 +257,19 @@ impl<'a> IndicatorButton<'a> {
         self.behavior = behavior;
         self
     }
+
+    /// Appearance of each state, indexed by the bound value. Bool bindings use
+    /// the first two entries for off and on.
+    pub fn states(mut self, states: Vec<IndicatorState>) -> Self {
+        self.states = states;
+        self
+    }
+
+    /// How long [`IndicatorButtonBehavior::Pulse`] stays on, in seconds.
+    pub fn pulse_duration(mut self, pulse_duration: impl Into<f32>) -> Self {
+        self.pulse_duration = pulse_duration.into();
+        self
+    }
 }
 
 impl<'a> Widget for IndicatorButton<'a> {
@@ -138,15 +288,18 @@ impl<'a> Widget for IndicatorButton<'a> {
         match self.behavior {
             IndicatorButtonBehavior::Toggle => {
                 if response.clicked() {
-                    let value = get(&mut self.get_set_value);
-                    set(&mut self.get_set_value, !value);
+                    let state = get(&mut self.get_set_value);
+                    set(
+                        &mut self.get_set_value,
+                        next_state(state, self.states.len()),
+                    );
 
                     response.mark_changed();
                 }
             }
             IndicatorButtonBehavior::Hold => {
                 if response.drag_started() || response.drag_stopped() {
-                    set(&mut self.get_set_value, response.dragged());
+                    set(&mut self.get_set_value, response.dragged() as usize);
                     response.mark_changed();
                 }
 
@@ -154,14 +307,47 @@ impl<'a> Widget for IndicatorButton<'a> {
                     if ui.ctx().input(|input| input.key_pressed(Key::Enter))
                         || ui.ctx().input(|input| input.key_pressed(Key::Space))
                     {
-                        set(&mut self.get_set_value, true);
+                        set(&mut self.get_set_value, 1);
                         response.mark_changed();
                     }
 
                     if ui.ctx().input(|input| input.key_released(Key::Enter))
                         || ui.ctx().input(|input| input.key_released(Key::Space))
                     {
-                        set(&mut self.get_set_value, false);
+                        set(&mut self.get_set_value, 0);
+                        response.mark_changed();
+                    }
+                }
+            }
+            IndicatorButtonBehavior::Radio => {
+                if response.clicked() && (get(&mut self.get_set_value) == 0) {
+                    set(&mut self.get_set_value, 1);
+                    response.mark_changed();
+                }
+            }
+            IndicatorButtonBehavior::Pulse => {
+                let time = ui.input(|input| input.time);
+
+                if response.clicked() {
+                    ui.memory_mut(|memory| memory.data.insert_temp(response.id, time));
+
+                    if get(&mut self.get_set_value) == 0 {
+                        set(&mut self.get_set_value, 1);
+                        response.mark_changed();
+                    }
+                }
+
+                if get(&mut self.get_set_value) != 0 {
+                    let pulse_start = ui
+                        .memory(|memory| memory.data.get_temp::<f64>(response.id))
+                        .unwrap_or(f64::NEG_INFINITY);
+                    let remaining = pulse_start + self.pulse_duration as f64 - time;
+
+                    if remaining > 0.0 {
+                        ui.ctx()
+                            .request_repaint_after(Duration::from_secs_f64(remaining));
+                    } else {
+                        set(&mut self.get_set_value, 0);
                         response.mark_changed();
                     }
                 }
@@ -169,18 +355,34 @@ impl<'a> Widget for IndicatorButton<'a> {
         }
 
         if ui.is_rect_visible(rect) {
+            let style = self.style.resolve(ui);
             let visuals = *ui.style().interact(&response);
 
+            let state = self
+                .states
+                .get(get(&mut self.get_set_value))
+                .copied()
+                .unwrap_or(IndicatorState::OFF);
+
+            let (blink_on, blink_change) = state.blink_pattern.phase(ui.input(|input| input.time));
+
+            if let Some(blink_change) = blink_change {
+                ui.ctx()
+                    .request_repaint_after(Duration::from_secs_f64(blink_change));
+            }
+
+            let level = if blink_on { state.level } else { 0.0 };
+
             let value = if self.animated {
                 ui.ctx()
-                    .animate_bool(response.id, get(&mut self.get_set_value))
+                    .animate_value_with_time(response.id, level, ui.style().animation_time)
             } else {
-                #[allow(clippy::collapsible_else_if)]
-                if get(&mut self.get_set_value) {
-                    1.0
-                } else {
-                    0.0
-                }
+                level
+            };
+
+            let style = DisplayStyle {
+                active_foreground_color: state.color.unwrap_or(style.active_foreground_color),
+                ..style
             };
 
             ui.painter()
@@ -197,14 +399,14 @@ impl<'a> Widget for IndicatorButton<'a> {
                 ui.painter().rect(
                     indicator_rect.shrink(margin),
                     4.0,
-                    self.style.background_color,
+                    style.background_color,
                     Stroke::NONE,
                 );
 
                 ui.painter().rect(
                     indicator_rect.shrink(margin + 2.0),
                     4.0,
-                    self.style.foreground_color_blend(value),
+                    style.foreground_color_blend(value),
                     Stroke::NONE,
                 );
             }
@@ -223,3 +425,42 @@ impl<'a> Widget for IndicatorButton<'a> {
         response
     }
 }
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggle_from_unlisted_enum_value() {
+        let mut value = 'z';
+        let mut button = IndicatorButton::enum_state(
+            &mut value,
+            vec![
+                ('a', IndicatorState::OFF),
+                ('b', IndicatorState::ON),
+                ('c', IndicatorState::ON),
+            ],
+        );
+
+        let state = get(&mut button.get_set_value);
+        assert_eq!(state, 3);
+        set(
+            &mut button.get_set_value,
+            next_state(state, button.states.len()),
+        );
+        assert_eq!(get(&mut button.get_set_value), 0);
+
+        drop(button);
+        assert_eq!(value, 'a');
+    }
+
+    #[test]
+    fn next_state_wraps() {
+        assert_eq!(next_state(0, 3), 1);
+        assert_eq!(next_state(2, 3), 0);
+        assert_eq!(next_state(0, 0), 1);
+        assert_eq!(next_state(1, 0), 0);
+    }
+}
@@ -1,10 +1,174 @@
+use std::collections::VecDeque;
 use std::ops::RangeInclusive;
+use std::time::Duration;
 
-use egui::{self, remap_clamp, Response, Sense, Ui, Widget};
-use emath::Vec2;
-use epaint::Stroke;
+use egui::{
+    self, lerp, remap_clamp, Align2, Color32, CursorIcon, Response, Rgba, RichText, Sense,
+    TextStyle, Ui, Widget,
+};
+use emath::{vec2, Pos2, Rect, Vec2};
+use epaint::{Hsva, Stroke};
+use strum::{Display, EnumIter};
 
-use crate::displays::{DisplayStyle, DisplayStylePreset};
+use crate::common::Orientation;
+use crate::displays::{DisplayStyle, DisplayStylePreset, ThemedDisplayStyle};
+use crate::polygon::concave_polygon;
+
+// ----------------------------------------------------------------------------
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Display, PartialEq)]
+pub enum LedShape {
+    #[strum(to_string = "Circle")]
+    Circle,
+
+    #[strum(to_string = "Square")]
+    Square,
+
+    /// Horizontal rectangle, `diameter` wide.
+    #[strum(to_string = "Bar")]
+    Bar,
+
+    /// Triangle pointing towards the given direction.
+    #[strum(to_string = "Arrow {0}")]
+    Arrow(Orientation),
+}
+
+impl LedShape {
+    fn size(&self, diameter: f32) -> Vec2 {
+        match *self {
+            LedShape::Bar => vec2(diameter, diameter * 0.4),
+            _ => Vec2::splat(diameter),
+        }
+    }
+
+    fn outline(&self, rect: Rect) -> Vec<Pos2> {
+        match *self {
+            LedShape::Circle => Vec::new(),
+            LedShape::Square | LedShape::Bar => vec![
+                rect.left_top(),
+                rect.right_top(),
+                rect.right_bottom(),
+                rect.left_bottom(),
+            ],
+            LedShape::Arrow(orientation) => {
+                let rot = orientation.rot2();
+                let radius = rect.width().min(rect.height()) / 2.0;
+
+                [
+                    vec2(1.0, 0.0),
+                    vec2(-0.5, 0.75_f32.sqrt()),
+                    vec2(-0.5, -(0.75_f32.sqrt())),
+                ]
+                .into_iter()
+                .map(|corner| rect.center() + rot * (radius * corner))
+                .collect()
+            }
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Display, EnumIter, PartialEq)]
+pub enum LedBlinkPattern {
+    #[strum(to_string = "Steady")]
+    Steady,
+
+    /// 0.5 Hz.
+    #[strum(to_string = "Slow")]
+    Slow,
+
+    /// 2 Hz.
+    #[strum(to_string = "Fast")]
+    Fast,
+
+    /// Two short flashes per second.
+    #[strum(to_string = "Heartbeat")]
+    Heartbeat,
+
+    /// On and off durations in seconds.
+    #[strum(to_string = "Custom")]
+    Custom { on: f32, off: f32 },
+}
+
+impl LedBlinkPattern {
+    /// Alternating on and off durations, starting with on.
+    fn durations(&self) -> Vec<f64> {
+        match *self {
+            LedBlinkPattern::Steady => Vec::new(),
+            LedBlinkPattern::Slow => vec![1.0, 1.0],
+            LedBlinkPattern::Fast => vec![0.25, 0.25],
+            LedBlinkPattern::Heartbeat => vec![0.1, 0.15, 0.1, 0.65],
+            LedBlinkPattern::Custom { on, off } => vec![on.max(0.0) as f64, off.max(0.0) as f64],
+        }
+    }
+
+    /// Whether the LED is lit at `time`, and the time left until that
+    /// changes.
+    pub(crate) fn phase(&self, time: f64) -> (bool, Option<f64>) {
+        let durations = self.durations();
+        let period = durations.iter().sum::<f64>();
+
+        if period <= 0.0 {
+            return (true, None);
+        }
+
+        let mut time_in_period = time % period;
+        for (index, duration) in durations.into_iter().enumerate() {
+            if time_in_period < duration {
+                return (index % 2 == 0, Some(duration - time_in_period));
+            }
+            time_in_period -= duration;
+        }
+
+        (true, None)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Display, EnumIter, Eq, PartialEq)]
+pub enum LedLabelPosition {
+    #[strum(to_string = "Right")]
+    Right,
+
+    #[strum(to_string = "Left")]
+    Left,
+
+    #[strum(to_string = "Below")]
+    Below,
+}
+
+/// Recent values with the time they were first shown, oldest first.
+#[derive(Clone, Default)]
+struct LedHistory {
+    changes: VecDeque<(f64, f32)>,
+}
+
+impl LedHistory {
+    fn record(&mut self, time: f64, value: f32, capacity: usize) {
+        if self.changes.back().map(|&(_, last_value)| last_value) != Some(value) {
+            self.changes.push_back((time, value));
+        }
+
+        while self.changes.len() > capacity {
+            self.changes.pop_front();
+        }
+    }
+}
+
+fn format_age(seconds: f64) -> String {
+    match seconds {
+        s if s < 60.0 => format!("{s:.0} s ago"),
+        s if s < 3600.0 => format!("{:.0} min ago", s / 60.0),
+        s => format!("{:.1} h ago", s / 3600.0),
+    }
+}
+
+// ----------------------------------------------------------------------------
 
 #[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
 pub struct LedDisplay {
@@ -12,7 +176,14 @@ pub struct LedDisplay {
     diameter: f32,
     padding: f32,
     range: RangeInclusive<f32>,
-    style: DisplayStyle,
+    style: ThemedDisplayStyle,
+    color: Option<Color32>,
+    blink_pattern: LedBlinkPattern,
+    shape: LedShape,
+    label: Option<String>,
+    label_position: LedLabelPosition,
+    interactive: bool,
+    history_length: usize,
     animated: bool,
 }
 
@@ -23,7 +194,14 @@ impl LedDisplay {
             diameter: 16.0,
             padding: 0.25,
             range: 0.0..=1.0,
-            style: DisplayStylePreset::Default.style(),
+            style: ThemedDisplayStyle::default(),
+            color: None,
+            blink_pattern: LedBlinkPattern::Steady,
+            shape: LedShape::Circle,
+            label: None,
+            label_position: LedLabelPosition::Right,
+            interactive: false,
+            history_length: 0,
             animated: true,
         }
     }
@@ -48,12 +226,72 @@ impl LedDisplay {
     }
 
     pub fn style(mut self, style: DisplayStyle) -> Self {
-        self.style = style;
+        self.style = ThemedDisplayStyle::Fixed(style);
         self
     }
 
     pub fn style_preset(mut self, preset: DisplayStylePreset) -> Self {
-        self.style = preset.style();
+        self.style = ThemedDisplayStyle::Preset(preset);
+        self
+    }
+
+    /// Uses the style registered under `name` in the
+    /// [`DisplayStyleRegistry`](crate::displays::DisplayStyleRegistry).
+    pub fn style_name(mut self, name: impl Into<String>) -> Self {
+        self.style = ThemedDisplayStyle::Named(name.into());
+        self
+    }
+
+    /// Lit color of an RGB LED, replacing the style's active foreground color.
+    pub fn color(mut self, color: Color32) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Lit color of an RGB LED at full saturation, `hue` from `0.0` to `1.0`.
+    pub fn hue(self, hue: f32) -> Self {
+        self.color(Color32::from(Hsva::new(hue.rem_euclid(1.0), 1.0, 1.0, 1.0)))
+    }
+
+    /// Lit color of a bi-color LED, mixing from `first` at `0.0` to `second`
+    /// at `1.0` like driving both dies at once.
+    pub fn bicolor(self, first: Color32, second: Color32, mix: f32) -> Self {
+        self.color(Color32::from(lerp(
+            Rgba::from(first)..=Rgba::from(second),
+            mix.clamp(0.0, 1.0),
+        )))
+    }
+
+    pub fn blink_pattern(mut self, blink_pattern: LedBlinkPattern) -> Self {
+        self.blink_pattern = blink_pattern;
+        self
+    }
+
+    pub fn shape(mut self, shape: LedShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    pub fn label(mut self, label: impl ToString) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    pub fn label_position(mut self, label_position: LedLabelPosition) -> Self {
+        self.label_position = label_position;
+        self
+    }
+
+    /// Senses clicks, e.g. for acknowledging an alarm.
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    /// Number of recent value changes listed in the hover tooltip. `0`
+    /// disables the history.
+    pub fn history_length(mut self, history_length: usize) -> Self {
+        self.history_length = history_length;
         self
     }
 
@@ -61,39 +299,176 @@ impl LedDisplay {
         self.animated = animated;
         self
     }
+
+    fn value_text(&self, value: f32) -> String {
+        if value == *self.range.start() {
+            "Off".to_owned()
+        } else if value == *self.range.end() {
+            "On".to_owned()
+        } else {
+            format!("{value}")
+        }
+    }
+
+    fn history_ui(&self, ui: &mut Ui, history: &LedHistory) {
+        let time = ui.input(|input| input.time);
+        ui.ctx().request_repaint_after(Duration::from_secs(1));
+
+        if let Some(label) = &self.label {
+            ui.label(RichText::new(label).strong());
+        }
+
+        egui::Grid::new("led_history").show(ui, |ui| {
+            for &(change_time, value) in history.changes.iter().rev() {
+                ui.label(self.value_text(value));
+                ui.label(format_age(time - change_time));
+                ui.end_row();
+            }
+        });
+    }
 }
 
 impl Widget for LedDisplay {
     fn ui(self, ui: &mut Ui) -> Response {
-        let desired_size = Vec2::splat(self.diameter + self.padding * self.diameter);
+        let led_size = self.shape.size(self.diameter);
+        let led_box_size = led_size + Vec2::splat(self.padding * self.diameter);
+
+        let label_galley = self.label.as_ref().map(|label| {
+            ui.painter().layout_no_wrap(
+                label.clone(),
+                TextStyle::Body.resolve(ui.style()),
+                ui.visuals().text_color(),
+            )
+        });
+        let label_spacing = ui.spacing().item_spacing;
+
+        let desired_size = match label_galley {
+            None => led_box_size,
+            Some(ref galley) => match self.label_position {
+                LedLabelPosition::Right | LedLabelPosition::Left => vec2(
+                    led_box_size.x + label_spacing.x + galley.size().x,
+                    led_box_size.y.max(galley.size().y),
+                ),
+                LedLabelPosition::Below => vec2(
+                    led_box_size.x.max(galley.size().x),
+                    led_box_size.y + label_spacing.y + galley.size().y,
+                ),
+            },
+        };
+
+        let (outer_rect, mut response) = ui.allocate_exact_size(
+            desired_size,
+            if self.interactive {
+                Sense::click()
+            } else {
+                Sense::hover()
+            },
+        );
+
+        let (led_align, label_align) = match self.label_position {
+            LedLabelPosition::Right => (Align2::LEFT_CENTER, Align2::RIGHT_CENTER),
+            LedLabelPosition::Left => (Align2::RIGHT_CENTER, Align2::LEFT_CENTER),
+            LedLabelPosition::Below => (Align2::CENTER_TOP, Align2::CENTER_BOTTOM),
+        };
+        let rect = led_align.align_size_within_rect(led_box_size, outer_rect);
+
+        if self.interactive {
+            response = response.on_hover_cursor(CursorIcon::PointingHand);
+        }
+
+        if self.history_length > 0 {
+            let history_id = response.id.with("history");
+            let time = ui.input(|input| input.time);
+
+            let mut history = ui
+                .memory(|memory| memory.data.get_temp::<LedHistory>(history_id))
+                .unwrap_or_default();
+            history.record(time, self.value, self.history_length);
+            ui.memory_mut(|memory| memory.data.insert_temp(history_id, history.clone()));
+
+            response = response.on_hover_ui(|ui| self.history_ui(ui, &history));
+        }
+
+        let (blink_on, blink_change) = self.blink_pattern.phase(ui.input(|input| input.time));
+
+        if let Some(blink_change) = blink_change {
+            ui.ctx()
+                .request_repaint_after(Duration::from_secs_f64(blink_change));
+        }
 
-        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+        let value = if blink_on {
+            self.value
+        } else {
+            *self.range.start()
+        };
 
         if ui.is_rect_visible(rect) {
             let value = remap_clamp(
                 if self.animated {
-                    ui.ctx()
-                        .animate_value_with_time(response.id, self.value, 0.1)
+                    ui.ctx().animate_value_with_time(response.id, value, 0.1)
                 } else {
-                    self.value
+                    value
                 },
                 self.range,
                 0.0..=1.0,
             );
 
+            let style = self.style.resolve(ui);
+            let style = DisplayStyle {
+                active_foreground_color: self.color.unwrap_or(style.active_foreground_color),
+                ..style
+            };
+
             ui.painter().rect(
                 rect,
                 ui.style().visuals.noninteractive().rounding,
-                self.style.background_color,
+                style.background_color,
                 Stroke::NONE,
             );
 
-            ui.painter().circle(
-                rect.center(),
-                self.diameter / 2.0,
-                self.style.foreground_color_blend(value),
-                self.style.foreground_stroke_blend(value),
+            let led_rect = Rect::from_center_size(rect.center(), led_size);
+
+            match self.shape {
+                LedShape::Circle => {
+                    style.paint_glow_circle(
+                        ui.painter(),
+                        rect.center(),
+                        self.diameter / 2.0,
+                        value,
+                    );
+
+                    ui.painter().circle(
+                        rect.center(),
+                        self.diameter / 2.0,
+                        style.foreground_color_blend(value),
+                        style.foreground_stroke_blend(value),
+                    );
+                }
+                shape => {
+                    let outline = shape.outline(led_rect);
+
+                    style.paint_glow(ui.painter(), &outline, value);
+
+                    ui.painter().add(concave_polygon(
+                        ui.painter(),
+                        outline,
+                        style.foreground_color_blend(value),
+                        style.foreground_stroke_blend(value),
+                    ));
+                }
+            }
+
+            style.paint_reflection(
+                ui.painter(),
+                rect,
+                ui.style().visuals.noninteractive().rounding,
             );
+
+            if let Some(galley) = label_galley {
+                let label_rect = label_align.align_size_within_rect(galley.size(), outer_rect);
+                ui.painter()
+                    .galley(label_rect.min, galley, ui.visuals().text_color());
+            }
         }
 
         response
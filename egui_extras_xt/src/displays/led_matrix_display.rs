@@ -0,0 +1,352 @@
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+use egui::{vec2, Color32, Mesh, Pos2, Rect, Response, Sense, Ui, Vec2, Widget};
+use epaint::Stroke;
+use strum::{Display, EnumIter};
+
+use crate::displays::character_display::{rom_pattern, CharacterPattern};
+use crate::displays::{DisplayStyle, DisplayStylePreset, ThemedDisplayStyle};
+
+// ----------------------------------------------------------------------------
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Display, EnumIter, Eq, PartialEq)]
+pub enum LedPixelShape {
+    #[strum(to_string = "Round")]
+    Round,
+
+    #[strum(to_string = "Square")]
+    Square,
+}
+
+/// Width of a text glyph including the blank column after it.
+const GLYPH_PITCH: usize = 6;
+const GLYPH_ROWS: usize = 8;
+
+/// Vertices of the polygon approximating a round pixel.
+const ROUND_PIXEL_VERTICES: usize = 12;
+
+// ----------------------------------------------------------------------------
+
+/// Grid of LEDs showing a bitmap, per-pixel intensities or colors, and
+/// optionally scrolling text in the 5x8 font of [`CharacterDisplayWidget`].
+///
+/// All pixels are painted as a single mesh.
+///
+/// [`CharacterDisplayWidget`]: crate::displays::CharacterDisplayWidget
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct LedMatrixDisplay {
+    columns: usize,
+    rows: usize,
+    intensities: Vec<f32>,
+    colors: Option<Vec<Color32>>,
+    text: String,
+    text_row: Option<usize>,
+    scroll_speed: f32,
+    pixel_size: f32,
+    pixel_spacing: f32,
+    pixel_shape: LedPixelShape,
+    style: ThemedDisplayStyle,
+}
+
+impl LedMatrixDisplay {
+    pub fn new(columns: usize, rows: usize) -> Self {
+        Self {
+            columns,
+            rows,
+            intensities: vec![0.0; columns * rows],
+            colors: None,
+            text: String::new(),
+            text_row: None,
+            scroll_speed: 0.0,
+            pixel_size: 6.0,
+            pixel_spacing: 0.25,
+            pixel_shape: LedPixelShape::Round,
+            style: ThemedDisplayStyle::default(),
+        }
+    }
+
+    /// On/off pixels in row-major order.
+    pub fn bitmap(self, bitmap: impl IntoIterator<Item = bool>) -> Self {
+        self.intensities(bitmap.into_iter().map(|lit| if lit { 1.0 } else { 0.0 }))
+    }
+
+    /// Pixel intensities from `0.0` to `1.0` in row-major order.
+    pub fn intensities(mut self, intensities: impl IntoIterator<Item = f32>) -> Self {
+        for (pixel, intensity) in self.intensities.iter_mut().zip(intensities) {
+            *pixel = intensity.clamp(0.0, 1.0);
+        }
+        self
+    }
+
+    /// Lit color of each pixel in row-major order, replacing the style's
+    /// active foreground color. Pixels are lit unless their color is
+    /// [`Color32::TRANSPARENT`]; call [`Self::intensities`] afterwards to dim
+    /// them.
+    pub fn colors(mut self, colors: impl IntoIterator<Item = Color32>) -> Self {
+        let colors = colors
+            .into_iter()
+            .chain(std::iter::repeat(Color32::TRANSPARENT))
+            .take(self.columns * self.rows)
+            .collect::<Vec<_>>();
+
+        for (intensity, color) in self.intensities.iter_mut().zip(&colors) {
+            *intensity = if *color == Color32::TRANSPARENT {
+                0.0
+            } else {
+                1.0
+            };
+        }
+
+        self.colors = Some(colors);
+        self
+    }
+
+    pub fn set_pixel(mut self, column: usize, row: usize, intensity: f32) -> Self {
+        if (column < self.columns) && (row < self.rows) {
+            self.intensities[row * self.columns + column] = intensity.clamp(0.0, 1.0);
+        }
+        self
+    }
+
+    /// Text drawn over the bitmap. Lines past the first are ignored.
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Top row of the text, vertically centered by default.
+    pub fn text_row(mut self, text_row: usize) -> Self {
+        self.text_row = Some(text_row);
+        self
+    }
+
+    /// Scrolls the text from right to left, in columns per second. `0.0`
+    /// keeps it still.
+    pub fn scroll_speed(mut self, scroll_speed: impl Into<f32>) -> Self {
+        self.scroll_speed = scroll_speed.into();
+        self
+    }
+
+    pub fn pixel_size(mut self, pixel_size: impl Into<f32>) -> Self {
+        self.pixel_size = pixel_size.into();
+        self
+    }
+
+    /// Gap between pixels, relative to the pixel size.
+    pub fn pixel_spacing(mut self, pixel_spacing: impl Into<f32>) -> Self {
+        self.pixel_spacing = pixel_spacing.into();
+        self
+    }
+
+    pub fn pixel_shape(mut self, pixel_shape: LedPixelShape) -> Self {
+        self.pixel_shape = pixel_shape;
+        self
+    }
+
+    pub fn style(mut self, style: DisplayStyle) -> Self {
+        self.style = ThemedDisplayStyle::Fixed(style);
+        self
+    }
+
+    pub fn style_preset(mut self, preset: DisplayStylePreset) -> Self {
+        self.style = ThemedDisplayStyle::Preset(preset);
+        self
+    }
+
+    /// Uses the style registered under `name` in the
+    /// [`DisplayStyleRegistry`](crate::displays::DisplayStyleRegistry).
+    pub fn style_name(mut self, name: impl Into<String>) -> Self {
+        self.style = ThemedDisplayStyle::Named(name.into());
+        self
+    }
+
+    fn pixel_pitch(&self) -> f32 {
+        self.pixel_size * (1.0 + self.pixel_spacing)
+    }
+
+    /// Rasterizes the text at `offset` columns into `intensities`.
+    fn draw_text(&self, intensities: &mut [f32], offset: i64) {
+        let text_row = self
+            .text_row
+            .unwrap_or(self.rows.saturating_sub(GLYPH_ROWS - 1) / 2);
+
+        let patterns = self
+            .text
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .map(|c| {
+                rom_pattern(c)
+                    .or_else(|| rom_pattern('?'))
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<CharacterPattern>>();
+
+        for column in 0..self.columns {
+            let text_column = column as i64 - offset;
+            if text_column < 0 {
+                continue;
+            }
+
+            let (glyph_index, glyph_column) = (
+                text_column as usize / GLYPH_PITCH,
+                text_column as usize % GLYPH_PITCH,
+            );
+
+            let Some(pattern) = patterns.get(glyph_index) else {
+                break;
+            };
+
+            if glyph_column >= GLYPH_PITCH - 1 {
+                continue;
+            }
+
+            for (glyph_row, pattern_row) in pattern.iter().enumerate() {
+                let row = text_row + glyph_row;
+                if (row < self.rows) && ((pattern_row >> (4 - glyph_column)) & 0x01 != 0x00) {
+                    intensities[row * self.columns + column] = 1.0;
+                }
+            }
+        }
+    }
+
+    fn add_pixel(&self, mesh: &mut Mesh, center: Pos2, radius: f32, color: Color32) {
+        let first_vertex = mesh.vertices.len() as u32;
+
+        match self.pixel_shape {
+            LedPixelShape::Square => {
+                let rect = Rect::from_center_size(center, Vec2::splat(2.0 * radius));
+                mesh.add_colored_rect(rect, color);
+            }
+            LedPixelShape::Round => {
+                mesh.colored_vertex(center, color);
+                for vertex_index in 0..ROUND_PIXEL_VERTICES {
+                    let angle = TAU * (vertex_index as f32 / ROUND_PIXEL_VERTICES as f32);
+                    mesh.colored_vertex(center + radius * Vec2::angled(angle), color);
+                }
+                for vertex_index in 0..ROUND_PIXEL_VERTICES as u32 {
+                    mesh.add_triangle(
+                        first_vertex,
+                        first_vertex + 1 + vertex_index,
+                        first_vertex + 1 + (vertex_index + 1) % ROUND_PIXEL_VERTICES as u32,
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl Widget for LedMatrixDisplay {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let pixel_pitch = self.pixel_pitch();
+        let margin = Vec2::splat(pixel_pitch);
+
+        let desired_size = vec2(self.columns as f32, self.rows as f32) * pixel_pitch + 2.0 * margin;
+
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        if ui.is_rect_visible(rect) {
+            let style = self.style.resolve(ui);
+
+            ui.painter().rect(
+                rect,
+                ui.style().visuals.noninteractive().rounding,
+                style.background_color,
+                Stroke::NONE,
+            );
+
+            let mut intensities = self.intensities.clone();
+
+            if !self.text.is_empty() {
+                let offset = if self.scroll_speed > 0.0 {
+                    let text_width =
+                        self.text.lines().next().unwrap_or_default().chars().count() * GLYPH_PITCH;
+                    let scroll_length = (text_width + self.columns) as f64;
+                    let scroll_speed = self.scroll_speed as f64;
+
+                    let time = ui.input(|input| input.time);
+                    ui.ctx().request_repaint_after(Duration::from_secs_f64(
+                        (1.0 - (time * scroll_speed).fract()) / scroll_speed,
+                    ));
+
+                    self.columns as i64 - ((time * scroll_speed) % scroll_length) as i64
+                } else {
+                    0
+                };
+
+                self.draw_text(&mut intensities, offset);
+            }
+
+            let origin = rect.min + margin + Vec2::splat(pixel_pitch / 2.0);
+            let radius = self.pixel_size / 2.0;
+
+            let mut mesh = Mesh::default();
+
+            let has_glow = style.effects.has_glow();
+            let vertices_per_pixel = match self.pixel_shape {
+                LedPixelShape::Square => 4,
+                LedPixelShape::Round => ROUND_PIXEL_VERTICES + 1,
+            };
+            mesh.reserve_vertices(intensities.len() * vertices_per_pixel * (1 + has_glow as usize));
+
+            let pixel_colors = intensities
+                .iter()
+                .enumerate()
+                .map(|(pixel_index, &intensity)| {
+                    let pixel_style = match self.colors {
+                        Some(ref colors) => DisplayStyle {
+                            active_foreground_color: colors[pixel_index],
+                            ..style
+                        },
+                        None => style,
+                    };
+
+                    (
+                        pixel_style.active_foreground_color,
+                        pixel_style.foreground_color_blend(intensity),
+                    )
+                });
+
+            // Halos go first so that they end up beneath every pixel
+            let mut pixels = Vec::with_capacity(intensities.len());
+
+            for (pixel_index, (active_color, color)) in pixel_colors.enumerate() {
+                let center = origin
+                    + pixel_pitch
+                        * vec2(
+                            (pixel_index % self.columns) as f32,
+                            (pixel_index / self.columns) as f32,
+                        );
+                let intensity = intensities[pixel_index];
+
+                if has_glow && (intensity > 0.0) {
+                    self.add_pixel(
+                        &mut mesh,
+                        center,
+                        radius + style.effects.glow_radius,
+                        active_color.gamma_multiply(style.effects.glow_intensity * intensity),
+                    );
+                }
+
+                pixels.push((center, color));
+            }
+
+            for (center, color) in pixels {
+                self.add_pixel(&mut mesh, center, radius, color);
+            }
+
+            ui.painter().add(mesh);
+
+            style.paint_reflection(
+                ui.painter(),
+                rect,
+                ui.style().visuals.noninteractive().rounding,
+            );
+        }
+
+        response
+    }
+}
@@ -1,12 +1,33 @@
+mod bar_graph_display;
 mod display_style;
 mod indicator_button;
 mod led_display;
+mod led_matrix_display;
+mod peak_hold;
+mod spectrum_display;
 mod waveform_display;
 
+pub mod character_display;
 pub mod segmented_display;
 
-pub use display_style::{DisplayStyle, DisplayStylePreset};
-pub use indicator_button::{IndicatorButton, IndicatorButtonBehavior};
-pub use led_display::LedDisplay;
-pub use segmented_display::{DisplayKind, DisplayMetrics, SegmentedDisplayWidget};
-pub use waveform_display::{BufferLayout, SignalEdge, WaveformDisplayWidget};
+pub use bar_graph_display::{BarGraphDisplay, BarGraphOrientation, BarGraphScale, BarGraphZone};
+pub use character_display::CharacterDisplayWidget;
+pub use display_style::{
+    DisplayEffects, DisplayStyle, DisplayStylePreset, DisplayStyleRegistry, DisplayStyleVariants,
+    ThemedDisplayStyle,
+};
+pub use indicator_button::{IndicatorButton, IndicatorButtonBehavior, IndicatorState};
+pub use led_display::{LedBlinkPattern, LedDisplay, LedLabelPosition, LedShape};
+pub use led_matrix_display::{LedMatrixDisplay, LedPixelShape};
+pub use segmented_display::{
+    DisplayKind, DisplayMetrics, SegmentedDisplayEditor, SegmentedDisplayWidget,
+};
+pub use spectrum_display::{
+    SpectrogramColormap, SpectrogramOrientation, SpectrogramWidget, SpectrumAnalyzerWidget,
+    SpectrumStyle, WindowFunction,
+};
+pub use waveform_display::{
+    AmplitudeUnit, BufferLayout, ChannelLayout, PeakCache, SignalEdge, Trigger, TriggerMode,
+    TriggerSlope, TriggerStatus, VectorscopeMode, VectorscopeWidget, WaveformDisplayWidget,
+    WaveformView,
+};
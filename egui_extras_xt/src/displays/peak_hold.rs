@@ -0,0 +1,57 @@
+/// Highest level seen by a meter, and when it was reached.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct PeakHold {
+    pub level: f32,
+    pub time: f64,
+}
+
+impl PeakHold {
+    pub fn new(level: f32, time: f64) -> Self {
+        Self { level, time }
+    }
+
+    /// Level of the peak at `time`, held for `hold_time` seconds and then
+    /// falling by `decay_rate` per second.
+    pub fn decayed(&self, time: f64, hold_time: f32, decay_rate: f32) -> f32 {
+        let decay_time = (time - self.time - hold_time as f64).max(0.0) as f32;
+        self.level - decay_rate * decay_time
+    }
+
+    /// Holds `level` if it reaches the decayed peak, returns the level shown.
+    pub fn update(&mut self, level: f32, time: f64, hold_time: f32, decay_rate: f32) -> f32 {
+        let peak = self.decayed(time, hold_time, decay_rate);
+
+        if level >= peak {
+            *self = Self::new(level, time);
+            level
+        } else {
+            peak
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decayed() {
+        let peak = PeakHold::new(0.8, 10.0);
+        assert_eq!(peak.decayed(10.5, 1.0, 0.5), 0.8);
+        assert_eq!(peak.decayed(11.0, 1.0, 0.5), 0.8);
+        assert!((peak.decayed(12.0, 1.0, 0.5) - 0.3).abs() < 1e-6);
+
+        assert_eq!(peak.decayed(10.0, 1.0, 0.0), 0.8);
+        assert_eq!(peak.decayed(100.0, 1.0, 0.0), 0.8);
+    }
+
+    #[test]
+    fn update() {
+        let mut peak = PeakHold::new(0.8, 10.0);
+        assert_eq!(peak.update(0.5, 10.5, 1.0, 0.5), 0.8);
+        assert_eq!(peak.update(0.5, 12.0, 1.0, 0.5), 0.5);
+        assert_eq!(peak.time, 12.0);
+    }
+}
@@ -0,0 +1,359 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::{Chars, FromStr};
+
+use crate::displays::segmented_display::DisplayGlyph;
+
+// ----------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DisplayFontError {
+    Syntax { line: usize },
+    InvalidCharacter { line: usize },
+    InvalidGlyph { line: usize },
+}
+
+impl Display for DisplayFontError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DisplayFontError::Syntax { line } => {
+                write!(f, "line {line}: expected `\"<char>\" = <glyph>`")
+            }
+            DisplayFontError::InvalidCharacter { line } => {
+                write!(f, "line {line}: key must be a single character")
+            }
+            DisplayFontError::InvalidGlyph { line } => {
+                write!(f, "line {line}: glyph must be an unsigned integer")
+            }
+        }
+    }
+}
+
+impl Error for DisplayFontError {}
+
+// ----------------------------------------------------------------------------
+
+/// Character-to-glyph map layered on top of the built-in glyph tables of a
+/// [`DisplayKind`](crate::displays::DisplayKind).
+///
+/// Fonts can be built from code or parsed from a subset of TOML, one
+/// `"<char>" = <glyph>` entry per line:
+///
+/// ```toml
+/// # Greek capitals for 16-segment displays
+/// "Γ" = 0x00C3
+/// "Π" = 0x00CF
+/// ```
+///
+/// Keys may be basic strings with TOML escapes (`"\u0393"`), literal strings
+/// without escapes (`'\'`) or bare keys (`A`). Glyphs may be written in
+/// decimal, hexadecimal (`0x`), octal (`0o`) or binary (`0b`) notation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DisplayFont {
+    glyphs: BTreeMap<char, DisplayGlyph>,
+    inherit_builtin: bool,
+}
+
+impl Default for DisplayFont {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DisplayFont {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            glyphs: BTreeMap::new(),
+            inherit_builtin: true,
+        }
+    }
+
+    /// When disabled, characters missing from this font are not looked up in
+    /// the built-in glyph table.
+    #[must_use]
+    pub fn inherit_builtin(mut self, inherit_builtin: bool) -> Self {
+        self.inherit_builtin = inherit_builtin;
+        self
+    }
+
+    #[must_use]
+    pub fn with_glyph(mut self, c: char, glyph: DisplayGlyph) -> Self {
+        self.insert(c, glyph);
+        self
+    }
+
+    pub fn insert(&mut self, c: char, glyph: DisplayGlyph) -> Option<DisplayGlyph> {
+        self.glyphs.insert(c, glyph)
+    }
+
+    pub fn remove(&mut self, c: char) -> Option<DisplayGlyph> {
+        self.glyphs.remove(&c)
+    }
+
+    /// Merges `other` into this font, overriding existing entries.
+    pub fn extend(&mut self, other: &DisplayFont) {
+        self.glyphs.extend(other.glyphs.iter());
+    }
+
+    #[must_use]
+    pub fn glyph(&self, c: char) -> Option<DisplayGlyph> {
+        self.glyphs.get(&c).copied()
+    }
+
+    #[must_use]
+    pub fn inherits_builtin(&self) -> bool {
+        self.inherit_builtin
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (char, DisplayGlyph)> + '_ {
+        self.glyphs.iter().map(|(&c, &glyph)| (c, glyph))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.glyphs.is_empty()
+    }
+}
+
+impl FromIterator<(char, DisplayGlyph)> for DisplayFont {
+    fn from_iter<T: IntoIterator<Item = (char, DisplayGlyph)>>(iter: T) -> Self {
+        Self {
+            glyphs: iter.into_iter().collect(),
+            inherit_builtin: true,
+        }
+    }
+}
+
+impl FromStr for DisplayFont {
+    type Err = DisplayFontError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let mut font = DisplayFont::new();
+
+        for (line_index, line) in source.lines().enumerate() {
+            let line_number = line_index + 1;
+
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (key, value) = find_unquoted(line, '=')
+                .map(|index| (line[..index].trim(), line[index + 1..].trim()))
+                .ok_or(DisplayFontError::Syntax { line: line_number })?;
+
+            let c = parse_character(key)
+                .ok_or(DisplayFontError::InvalidCharacter { line: line_number })?;
+
+            let glyph =
+                parse_glyph(value).ok_or(DisplayFontError::InvalidGlyph { line: line_number })?;
+
+            font.insert(c, glyph);
+        }
+
+        Ok(font)
+    }
+}
+
+impl Display for DisplayFont {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (c, glyph) in self.iter() {
+            match c {
+                '"' | '\\' => writeln!(f, "\"\\{c}\" = 0x{glyph:04X}")?,
+                c if c.is_control() => writeln!(f, "\"\\u{:04X}\" = 0x{glyph:04X}", c as u32)?,
+                c => writeln!(f, "\"{c}\" = 0x{glyph:04X}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Byte index of the first `target` outside of quoted strings.
+fn find_unquoted(line: &str, target: char) -> Option<usize> {
+    let mut in_quotes = None;
+    let mut escaped = false;
+
+    for (index, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes == Some('"') => escaped = true,
+            '"' | '\'' if in_quotes == Some(c) => in_quotes = None,
+            '"' | '\'' if in_quotes.is_none() => in_quotes = Some(c),
+            c if (c == target) && in_quotes.is_none() => return Some(index),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn strip_comment(line: &str) -> &str {
+    match find_unquoted(line, '#') {
+        Some(index) => &line[..index],
+        None => line,
+    }
+}
+
+fn parse_character(key: &str) -> Option<char> {
+    let key = if let Some(inner) = key.strip_prefix('"').and_then(|key| key.strip_suffix('"')) {
+        unescape_basic_string(inner)?
+    } else if let Some(inner) = key
+        .strip_prefix('\'')
+        .and_then(|key| key.strip_suffix('\''))
+    {
+        // Literal strings have no escape sequences
+        (!inner.contains('\'')).then(|| inner.to_owned())?
+    } else if key
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        key.to_owned()
+    } else {
+        return None;
+    };
+
+    let mut chars = key.chars();
+    let c = chars.next()?;
+    chars.next().is_none().then_some(c)
+}
+
+fn unescape_basic_string(source: &str) -> Option<String> {
+    let mut chars = source.chars();
+    let mut result = String::new();
+
+    while let Some(c) = chars.next() {
+        result.push(match c {
+            '\\' => match chars.next()? {
+                'b' => '\u{0008}',
+                't' => '\t',
+                'n' => '\n',
+                'f' => '\u{000C}',
+                'r' => '\r',
+                '"' => '"',
+                '\\' => '\\',
+                'u' => unescape_code_point(&mut chars, 4)?,
+                'U' => unescape_code_point(&mut chars, 8)?,
+                _ => return None,
+            },
+            '"' => return None,
+            c => c,
+        });
+    }
+
+    Some(result)
+}
+
+/// Parses the `\uXXXX` and `\UXXXXXXXX` escapes, which take exactly 4 and 8
+/// hexadecimal digits.
+fn unescape_code_point(chars: &mut Chars<'_>, digits: usize) -> Option<char> {
+    let hex = chars.by_ref().take(digits).collect::<String>();
+
+    if hex.len() != digits || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    char::from_u32(u32::from_str_radix(&hex, 16).ok()?)
+}
+
+fn parse_glyph(value: &str) -> Option<DisplayGlyph> {
+    let value = value.replace('_', "");
+
+    if let Some(hex) = value.strip_prefix("0x") {
+        DisplayGlyph::from_str_radix(hex, 16).ok()
+    } else if let Some(oct) = value.strip_prefix("0o") {
+        DisplayGlyph::from_str_radix(oct, 8).ok()
+    } else if let Some(bin) = value.strip_prefix("0b") {
+        DisplayGlyph::from_str_radix(bin, 2).ok()
+    } else {
+        value.parse().ok()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let font = concat!(
+            "# Greek capitals\n",
+            "\"\\u0393\" = 0x00C3 # Gamma\n",
+            "'Π' = 0x00CF\n",
+            "'\\' = 0b1010\n",
+            "\"#\" = 0o17\n",
+            "A = 1_000\n",
+            "'=' = 0x0048\n",
+            "\"\\U0001F600\" = 42\n",
+        )
+        .parse::<DisplayFont>()
+        .unwrap();
+
+        assert_eq!(font.glyph('\u{0393}'), Some(0x00C3));
+        assert_eq!(font.glyph('\u{03A0}'), Some(0x00CF));
+        assert_eq!(font.glyph('\\'), Some(0b1010));
+        assert_eq!(font.glyph('#'), Some(0o17));
+        assert_eq!(font.glyph('A'), Some(1000));
+        assert_eq!(font.glyph('\u{1F600}'), Some(42));
+        assert_eq!(font.glyph('='), Some(0x0048));
+        assert_eq!(font.len(), 7);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |source: &str| source.parse::<DisplayFont>().unwrap_err();
+
+        assert_eq!(error("\n\"A\" 1"), DisplayFontError::Syntax { line: 2 });
+        assert_eq!(
+            error("\"AB\" = 1"),
+            DisplayFontError::InvalidCharacter { line: 1 }
+        );
+        assert_eq!(
+            error("'\\u0041' = 1"),
+            DisplayFontError::InvalidCharacter { line: 1 }
+        );
+        assert_eq!(
+            error("\"\\u41\" = 1"),
+            DisplayFontError::InvalidCharacter { line: 1 }
+        );
+        assert_eq!(
+            error("\"\\q\" = 1"),
+            DisplayFontError::InvalidCharacter { line: 1 }
+        );
+        assert_eq!(
+            error("\"A\" = x"),
+            DisplayFontError::InvalidGlyph { line: 1 }
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let font = DisplayFont::from_iter([
+            ('A', 0x0077),
+            ('"', 0x0022),
+            ('\\', 0x0040),
+            ('\'', 0x0002),
+            ('#', 0x0036),
+            ('=', 0x0048),
+            ('\n', 0x0001),
+            ('\u{007F}', 0x0080),
+            ('\u{0393}', 0x00C3),
+            ('\u{1F600}', 0xFFFF_FFFF),
+        ]);
+
+        let source = font.to_string();
+        assert!(source.contains("\"\\u000A\" = 0x0001"));
+        assert!(source.contains("\"\\u007F\" = 0x0080"));
+        assert_eq!(source.parse::<DisplayFont>(), Ok(font));
+    }
+}
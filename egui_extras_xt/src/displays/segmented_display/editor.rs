@@ -0,0 +1,437 @@
+use std::ops::RangeInclusive;
+
+use egui::{Event, EventFilter, Key, Response, Sense, Ui, Vec2, Widget};
+use itertools::Itertools;
+
+use crate::displays::segmented_display::{
+    DisplayBlink, DisplayKind, NumberFormat, SegmentedDisplayWidget,
+};
+use crate::displays::DisplayStyle;
+
+// ----------------------------------------------------------------------------
+
+enum EditValue<'a> {
+    Integer {
+        value: &'a mut i64,
+        format: NumberFormat,
+        range: RangeInclusive<i64>,
+    },
+    Text {
+        value: &'a mut String,
+        digit_count: usize,
+        charset: Vec<char>,
+    },
+}
+
+impl EditValue<'_> {
+    fn digit_count(&self) -> usize {
+        match self {
+            EditValue::Integer { format, .. } => format.digit_count,
+            EditValue::Text { digit_count, .. } => *digit_count,
+        }
+    }
+
+    fn text_chars(value: &str, digit_count: usize) -> Vec<char> {
+        value
+            .chars()
+            .pad_using(digit_count, |_| ' ')
+            .take(digit_count)
+            .collect_vec()
+    }
+
+    /// Replaces the character at `digit_index`, keeping anything past the
+    /// displayed digits.
+    fn replace_text_char(
+        value: &mut String,
+        digit_count: usize,
+        digit_index: usize,
+        c: char,
+    ) -> bool {
+        let mut chars = value.chars().pad_using(digit_count, |_| ' ').collect_vec();
+        chars[digit_index] = c;
+
+        let new_value = chars.into_iter().collect::<String>().trim_end().to_owned();
+        let changed = new_value != *value;
+        *value = new_value;
+        changed
+    }
+
+    /// Steps the digit at `digit_index` up or down by `delta`.
+    fn step(&mut self, digit_index: usize, delta: i64) -> bool {
+        match self {
+            EditValue::Integer {
+                value,
+                format,
+                range,
+            } => {
+                let place_value = (format.radix.radix() as i128)
+                    .saturating_pow((format.digit_count - 1 - digit_index) as u32);
+
+                let new_value = (**value as i128)
+                    .saturating_add(place_value.saturating_mul(delta as i128))
+                    .clamp(*range.start() as i128, *range.end() as i128)
+                    as i64;
+
+                let changed = new_value != **value;
+                **value = new_value;
+                changed
+            }
+            EditValue::Text {
+                value,
+                digit_count,
+                charset,
+            } => {
+                if charset.is_empty() {
+                    return false;
+                }
+
+                let current_char = Self::text_chars(value, *digit_count)[digit_index];
+                let charset_index = charset
+                    .iter()
+                    .position(|&c| c == current_char)
+                    .unwrap_or_default() as i64;
+
+                let c = charset[(charset_index + delta).rem_euclid(charset.len() as i64) as usize];
+                Self::replace_text_char(value, *digit_count, digit_index, c)
+            }
+        }
+    }
+
+    /// Replaces the digit at `digit_index` with a typed character, returns
+    /// `None` when the character is not accepted.
+    fn type_char(&mut self, digit_index: usize, c: char) -> Option<bool> {
+        match self {
+            EditValue::Integer {
+                value,
+                format,
+                range,
+            } => {
+                let radix = format.radix.radix();
+                let digit = c.to_digit(radix)? as i128;
+
+                let place_value =
+                    (radix as i128).saturating_pow((format.digit_count - 1 - digit_index) as u32);
+                let magnitude = (**value as i128).abs();
+                let current_digit = (magnitude / place_value) % (radix as i128);
+                let magnitude = magnitude + (digit - current_digit) * place_value;
+
+                let sign = if **value < 0 { -1 } else { 1 };
+                let new_value =
+                    (magnitude * sign).clamp(*range.start() as i128, *range.end() as i128) as i64;
+
+                let changed = new_value != **value;
+                **value = new_value;
+                Some(changed)
+            }
+            EditValue::Text {
+                value,
+                digit_count,
+                charset,
+            } => {
+                let c = [c, c.to_ascii_uppercase()]
+                    .into_iter()
+                    .find(|c| charset.contains(c))?;
+
+                Some(Self::replace_text_char(value, *digit_count, digit_index, c))
+            }
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Segmented display whose digits can be selected and edited one at a time
+/// with the mouse wheel, arrow keys or by typing, like setting the clock of a
+/// microwave oven.
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct SegmentedDisplayEditor<'a> {
+    value: EditValue<'a>,
+    display: SegmentedDisplayWidget,
+    selected_digit: Option<&'a mut Option<usize>>,
+    highlight_blink: DisplayBlink,
+    highlight_style: Option<DisplayStyle>,
+}
+
+impl<'a> SegmentedDisplayEditor<'a> {
+    /// Edits `value` as a fixed-point number laid out by `format`.
+    pub fn integer(value: &'a mut i64, format: NumberFormat) -> Self {
+        let max_value = (format.radix.radix() as i64)
+            .saturating_pow(format.digit_count as u32)
+            .saturating_sub(1);
+
+        Self::with_value(EditValue::Integer {
+            value,
+            format,
+            range: 0..=max_value,
+        })
+    }
+
+    pub fn text(value: &'a mut String, digit_count: usize) -> Self {
+        Self::with_value(EditValue::Text {
+            value,
+            digit_count,
+            charset: " 0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ-".chars().collect(),
+        })
+    }
+
+    fn with_value(value: EditValue<'a>) -> Self {
+        Self {
+            value,
+            display: SegmentedDisplayWidget::new(DisplayKind::SevenSegment),
+            selected_digit: None,
+            highlight_blink: DisplayBlink::ALL,
+            highlight_style: None,
+        }
+    }
+
+    /// Appearance of the display. Digits pushed into it are discarded.
+    pub fn display(mut self, display: SegmentedDisplayWidget) -> Self {
+        self.display = display;
+        self
+    }
+
+    /// Allowed values in integer mode. Ignored in text mode.
+    pub fn range(mut self, range: RangeInclusive<i64>) -> Self {
+        if let EditValue::Integer {
+            range: ref mut value_range,
+            ..
+        } = self.value
+        {
+            *value_range = range;
+        }
+        self
+    }
+
+    /// Characters cycled through by the mouse wheel and arrow keys in text
+    /// mode. Ignored in integer mode.
+    pub fn charset(mut self, charset: impl AsRef<str>) -> Self {
+        if let EditValue::Text {
+            charset: ref mut value_charset,
+            ..
+        } = self.value
+        {
+            *value_charset = charset.as_ref().chars().collect();
+        }
+        self
+    }
+
+    /// Binds the selected digit index to external state instead of the
+    /// widget's own memory.
+    pub fn selected_digit(mut self, selected_digit: &'a mut Option<usize>) -> Self {
+        self.selected_digit = Some(selected_digit);
+        self
+    }
+
+    pub fn highlight_blink(mut self, highlight_blink: DisplayBlink) -> Self {
+        self.highlight_blink = highlight_blink;
+        self
+    }
+
+    pub fn highlight_style(mut self, highlight_style: Option<DisplayStyle>) -> Self {
+        self.highlight_style = highlight_style;
+        self
+    }
+}
+
+impl Widget for SegmentedDisplayEditor<'_> {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        let digit_count = self.value.digit_count();
+        let display = self.display.clear_digits().digit_count(digit_count);
+
+        let (rect, mut response) = ui.allocate_exact_size(display.desired_size(), Sense::click());
+
+        let mut selected_digit = match self.selected_digit {
+            Some(ref selected_digit) => **selected_digit,
+            None => ui.memory(|memory| memory.data.get_temp::<usize>(response.id)),
+        }
+        .filter(|&digit_index| digit_index < digit_count);
+
+        let hovered_digit = response
+            .hover_pos()
+            .and_then(|pos| display.digit_index_at(rect, pos));
+
+        if response.clicked() {
+            selected_digit = hovered_digit;
+            response.request_focus();
+        }
+
+        let mut changed = false;
+
+        if let Some(hovered_digit) = hovered_digit {
+            let scroll_delta = ui.input(|input| input.raw_scroll_delta.y);
+
+            if scroll_delta != 0.0 {
+                selected_digit = Some(hovered_digit);
+                changed |= self
+                    .value
+                    .step(hovered_digit, if scroll_delta > 0.0 { 1 } else { -1 });
+
+                ui.input_mut(|input| input.smooth_scroll_delta = Vec2::ZERO);
+            }
+        }
+
+        if response.has_focus() {
+            ui.memory_mut(|memory| {
+                memory.set_focus_lock_filter(
+                    response.id,
+                    EventFilter {
+                        horizontal_arrows: true,
+                        vertical_arrows: true,
+                        ..Default::default()
+                    },
+                );
+            });
+
+            let events = ui.input(|input| input.events.clone());
+            let last_digit = digit_count.saturating_sub(1);
+
+            for event in events {
+                let Some(digit_index) = selected_digit else {
+                    if matches!(event, Event::Key { pressed: true, .. }) && (digit_count > 0) {
+                        selected_digit = Some(0);
+                    }
+                    continue;
+                };
+
+                match event {
+                    Event::Key {
+                        key, pressed: true, ..
+                    } => match key {
+                        Key::ArrowLeft => selected_digit = Some(digit_index.saturating_sub(1)),
+                        Key::ArrowRight => selected_digit = Some((digit_index + 1).min(last_digit)),
+                        Key::ArrowUp => changed |= self.value.step(digit_index, 1),
+                        Key::ArrowDown => changed |= self.value.step(digit_index, -1),
+                        Key::Escape => response.surrender_focus(),
+                        _ => {}
+                    },
+                    Event::Text(text) => {
+                        for c in text.chars() {
+                            let digit_index = selected_digit.unwrap_or_default();
+                            if let Some(char_changed) = self.value.type_char(digit_index, c) {
+                                changed |= char_changed;
+                                selected_digit = Some((digit_index + 1).min(last_digit));
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        match self.selected_digit {
+            Some(ref mut external_selected_digit) => **external_selected_digit = selected_digit,
+            None => ui.memory_mut(|memory| match selected_digit {
+                Some(selected_digit) => memory.data.insert_temp(response.id, selected_digit),
+                None => memory.data.remove::<usize>(response.id),
+            }),
+        }
+
+        if changed {
+            response.mark_changed();
+        }
+
+        let display = match self.value {
+            EditValue::Integer { value, format, .. } => display.push_integer(*value, format),
+            EditValue::Text {
+                value, digit_count, ..
+            } => display.push_layout(
+                EditValue::text_chars(value, digit_count)
+                    .into_iter()
+                    .map(|c| (c, false))
+                    .collect(),
+            ),
+        };
+
+        let highlighted_digit = selected_digit.filter(|_| response.has_focus());
+        let highlight_blink = self.highlight_blink;
+        let highlight_style = self.highlight_style;
+
+        display
+            .map_digits(|digit_index, digit| {
+                if Some(digit_index) == highlighted_digit {
+                    digit.blink = highlight_blink;
+                    digit.style = highlight_style.or(digit.style);
+                }
+            })
+            .paint(ui, rect, response.id);
+
+        response
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn integer(value: &mut i64, range: RangeInclusive<i64>) -> EditValue<'_> {
+        EditValue::Integer {
+            value,
+            format: NumberFormat::new(4),
+            range,
+        }
+    }
+
+    fn text(value: &mut String) -> EditValue<'_> {
+        EditValue::Text {
+            value,
+            digit_count: 3,
+            charset: " AB".chars().collect(),
+        }
+    }
+
+    #[test]
+    fn type_char_keeps_sign() {
+        let mut value = -25;
+        assert_eq!(
+            integer(&mut value, -999..=999).type_char(3, '3'),
+            Some(true)
+        );
+        assert_eq!(value, -23);
+
+        let mut value = 25;
+        assert_eq!(
+            integer(&mut value, -999..=999).type_char(2, '7'),
+            Some(true)
+        );
+        assert_eq!(value, 75);
+
+        let mut value = -25;
+        assert_eq!(integer(&mut value, -999..=999).type_char(3, 'x'), None);
+        assert_eq!(value, -25);
+    }
+
+    #[test]
+    fn type_char_clamps_to_range() {
+        let mut value = -25;
+        assert_eq!(integer(&mut value, -50..=50).type_char(1, '9'), Some(true));
+        assert_eq!(value, -50);
+    }
+
+    #[test]
+    fn step() {
+        let mut value = -25;
+        assert!(integer(&mut value, -999..=999).step(2, 1));
+        assert_eq!(value, -15);
+
+        let mut value = 990;
+        assert!(integer(&mut value, -999..=999).step(1, 1));
+        assert_eq!(value, 999);
+
+        let mut value = String::from("AB");
+        assert!(text(&mut value).step(1, 1));
+        assert!(text(&mut value).step(2, -1));
+        assert_eq!(value, "A B");
+    }
+
+    #[test]
+    fn text_keeps_tail() {
+        let mut value = String::from("ABBA");
+        assert!(text(&mut value).step(0, 1));
+        assert_eq!(value, "BBBA");
+
+        assert_eq!(text(&mut value).type_char(2, 'a'), Some(true));
+        assert_eq!(value, "BBAA");
+    }
+}
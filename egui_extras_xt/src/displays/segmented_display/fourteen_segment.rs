@@ -0,0 +1,253 @@
+use egui::{pos2, Pos2};
+use itertools::Itertools;
+
+use crate::displays::segmented_display::{DisplayGlyph, DisplayImpl};
+
+// ----------------------------------------------------------------------------
+
+/// Glyph bits 0-5 drive the outer segments A-F, bits 6-7 the middle segments
+/// G1 and G2, and bits 8-13 the inner segments H (top left diagonal), J (top
+/// vertical), K (top right diagonal), L (bottom left diagonal), M (bottom
+/// vertical) and N (bottom right diagonal).
+#[derive(Clone, Copy, Default)]
+pub struct FourteenSegment;
+
+impl DisplayImpl for FourteenSegment {
+    fn segment_count(&self) -> usize {
+        14
+    }
+
+    fn glyph(&self, c: char) -> Option<DisplayGlyph> {
+        #[rustfmt::skip]
+        const GLYPH_DATA: &[(char, DisplayGlyph)] = &[
+            // Basic Latin
+            (' ',  0x0000), ('!',  0x1200), ('"',  0x0220), ('#',  0x12CE),
+            ('$',  0x12ED), ('%',  0x1EED), ('&',  0x235D), ('\'', 0x0020),
+            ('(',  0x2400), (')',  0x0900), ('*',  0x2DC0), ('+',  0x12C0),
+            (',',  0x0840), ('-',  0x00C0), ('.',  0x0008), ('/',  0x0C00),
+            ('0',  0x0C3F), ('1',  0x0406), ('2',  0x00DB), ('3',  0x00CF),
+            ('4',  0x00E6), ('5',  0x00ED), ('6',  0x00FD), ('7',  0x0007),
+            ('8',  0x00FF), ('9',  0x00EF), (':',  0x0048), (';',  0x0A00),
+            ('<',  0x2440), ('=',  0x00C8), ('>',  0x0980), ('?',  0x10A3),
+            ('@',  0x10BF), ('A',  0x00F7), ('B',  0x128F), ('C',  0x0039),
+            ('D',  0x120F), ('E',  0x0079), ('F',  0x0071), ('G',  0x00BD),
+            ('H',  0x00F6), ('I',  0x1209), ('J',  0x001F), ('K',  0x2470),
+            ('L',  0x0038), ('M',  0x0536), ('N',  0x2136), ('O',  0x003F),
+            ('P',  0x00F3), ('Q',  0x203F), ('R',  0x20F3), ('S',  0x018D),
+            ('T',  0x1201), ('U',  0x003E), ('V',  0x0C30), ('W',  0x2836),
+            ('X',  0x2D00), ('Y',  0x1500), ('Z',  0x0C09), ('[',  0x0039),
+            ('\\', 0x2100), (']',  0x000F), ('^',  0x0402), ('_',  0x0008),
+            ('`',  0x0100), ('a',  0x00DF), ('b',  0x1078), ('c',  0x0058),
+            ('d',  0x1258), ('e',  0x0858), ('f',  0x12C1), ('g',  0x208C),
+            ('h',  0x1070), ('i',  0x0010), ('j',  0x000C), ('k',  0x3280),
+            ('l',  0x0038), ('m',  0x10D4), ('n',  0x1050), ('o',  0x1058),
+            ('p',  0x0271), ('q',  0x1261), ('r',  0x0050), ('s',  0x2088),
+            ('t',  0x12C8), ('u',  0x1018), ('v',  0x0810), ('w',  0x2814),
+            ('x',  0x28C0), ('y',  0x200C), ('z',  0x0848), ('{',  0x1249),
+            ('|',  0x1200), ('}',  0x1289), ('~',  0x04C0),
+        ];
+
+        debug_assert!(GLYPH_DATA
+            .iter()
+            .map(|(k, _)| k)
+            .tuple_windows()
+            .all(|(k1, k2)| k1 < k2)); // is_sorted()
+
+        GLYPH_DATA
+            .binary_search_by_key(&c, |(k, _)| *k)
+            .ok()
+            .map(|index| GLYPH_DATA[index].1)
+    }
+
+    #[rustfmt::skip]
+    #[allow(unused_parens)]
+    fn geometry(
+        &self,
+        digit_width: f32,
+        digit_height: f32,
+        segment_thickness: f32,
+        segment_spacing: f32,
+        digit_median: f32,
+    ) -> Vec<Vec<Pos2>> {
+        vec![
+            vec![
+                pos2(-(digit_width / 2.0) + (segment_thickness / 4.0) + segment_spacing, -(digit_height / 2.0) + (segment_thickness / 4.0)                                 ),
+                pos2(-(digit_width / 2.0) + (segment_thickness / 2.0) + segment_spacing, -(digit_height / 2.0)                                                             ),
+                pos2( (digit_width / 2.0) - (segment_thickness / 2.0) - segment_spacing, -(digit_height / 2.0)                                                             ),
+                pos2( (digit_width / 2.0) - (segment_thickness / 4.0) - segment_spacing, -(digit_height / 2.0) + (segment_thickness / 4.0)                                 ),
+                pos2( (digit_width / 2.0) - (segment_thickness / 1.0) - segment_spacing, -(digit_height / 2.0) + (segment_thickness / 1.0)                                 ),
+                pos2(-(digit_width / 2.0) + (segment_thickness / 1.0) + segment_spacing, -(digit_height / 2.0) + (segment_thickness / 1.0)                                 ),
+            ],
+            vec![
+                pos2( (digit_width / 2.0) - (segment_thickness / 1.0)                  , -(digit_height / 2.0) + (segment_thickness / 1.0) + segment_spacing               ),
+                pos2( (digit_width / 2.0) - (segment_thickness / 4.0)                  , -(digit_height / 2.0) + (segment_thickness / 4.0) + segment_spacing               ),
+                pos2( (digit_width / 2.0)                                              , -(digit_height / 2.0) + (segment_thickness / 2.0) + segment_spacing               ),
+                pos2( (digit_width / 2.0)                                              ,                       - (segment_thickness / 2.0) - segment_spacing + digit_median),
+                pos2( (digit_width / 2.0) - (segment_thickness / 2.0)                  ,                                                   - segment_spacing + digit_median),
+                pos2( (digit_width / 2.0) - (segment_thickness / 1.0)                  ,                       - (segment_thickness / 2.0) - segment_spacing + digit_median),
+            ],
+            vec![
+                pos2( (digit_width / 2.0) - (segment_thickness / 1.0)                  ,  (digit_height / 2.0) - (segment_thickness / 1.0) - segment_spacing               ),
+                pos2( (digit_width / 2.0) - (segment_thickness / 4.0)                  ,  (digit_height / 2.0) - (segment_thickness / 4.0) - segment_spacing               ),
+                pos2( (digit_width / 2.0)                                              ,  (digit_height / 2.0) - (segment_thickness / 2.0) - segment_spacing               ),
+                pos2( (digit_width / 2.0)                                              ,                         (segment_thickness / 2.0) + segment_spacing + digit_median),
+                pos2( (digit_width / 2.0) - (segment_thickness / 2.0)                  ,                                                     segment_spacing + digit_median),
+                pos2( (digit_width / 2.0) - (segment_thickness / 1.0)                  ,                         (segment_thickness / 2.0) + segment_spacing + digit_median),
+            ],
+            vec![
+                pos2(-(digit_width / 2.0) + (segment_thickness / 4.0) + segment_spacing,  (digit_height / 2.0) - (segment_thickness / 4.0)                                 ),
+                pos2(-(digit_width / 2.0) + (segment_thickness / 2.0) + segment_spacing,  (digit_height / 2.0)                                                             ),
+                pos2( (digit_width / 2.0) - (segment_thickness / 2.0) - segment_spacing,  (digit_height / 2.0)                                                             ),
+                pos2( (digit_width / 2.0) - (segment_thickness / 4.0) - segment_spacing,  (digit_height / 2.0) - (segment_thickness / 4.0)                                 ),
+                pos2( (digit_width / 2.0) - (segment_thickness / 1.0) - segment_spacing,  (digit_height / 2.0) - (segment_thickness / 1.0)                                 ),
+                pos2(-(digit_width / 2.0) + (segment_thickness / 1.0) + segment_spacing,  (digit_height / 2.0) - (segment_thickness / 1.0)                                 ),
+            ],
+            vec![
+                pos2(-(digit_width / 2.0) + (segment_thickness / 1.0)                  ,  (digit_height / 2.0) - (segment_thickness / 1.0) - segment_spacing               ),
+                pos2(-(digit_width / 2.0) + (segment_thickness / 4.0)                  ,  (digit_height / 2.0) - (segment_thickness / 4.0) - segment_spacing               ),
+                pos2(-(digit_width / 2.0)                                              ,  (digit_height / 2.0) - (segment_thickness / 2.0) - segment_spacing               ),
+                pos2(-(digit_width / 2.0)                                              ,                         (segment_thickness / 2.0) + segment_spacing + digit_median),
+                pos2(-(digit_width / 2.0) + (segment_thickness / 2.0)                  ,                                                     segment_spacing + digit_median),
+                pos2(-(digit_width / 2.0) + (segment_thickness / 1.0)                  ,                         (segment_thickness / 2.0) + segment_spacing + digit_median),
+            ],
+            vec![
+                pos2(-(digit_width / 2.0) + (segment_thickness / 1.0)                  , -(digit_height / 2.0) + (segment_thickness / 1.0) + segment_spacing               ),
+                pos2(-(digit_width / 2.0) + (segment_thickness / 4.0)                  , -(digit_height / 2.0) + (segment_thickness / 4.0) + segment_spacing               ),
+                pos2(-(digit_width / 2.0)                                              , -(digit_height / 2.0) + (segment_thickness / 2.0) + segment_spacing               ),
+                pos2(-(digit_width / 2.0)                                              ,                       - (segment_thickness / 2.0) - segment_spacing + digit_median),
+                pos2(-(digit_width / 2.0) + (segment_thickness / 2.0)                  ,                                                   - segment_spacing + digit_median),
+                pos2(-(digit_width / 2.0) + (segment_thickness / 1.0)                  ,                       - (segment_thickness / 2.0) - segment_spacing + digit_median),
+            ],
+            vec![
+                pos2(                     - (segment_thickness / 1.0) - segment_spacing,                         (segment_thickness / 2.0)                   + digit_median),
+                pos2(                                                 - segment_spacing,                                                                       digit_median),
+                pos2(                     - (segment_thickness / 1.0) - segment_spacing,                       - (segment_thickness / 2.0)                   + digit_median),
+                pos2(-(digit_width / 2.0) + (segment_thickness / 1.0) + segment_spacing,                       - (segment_thickness / 2.0)                   + digit_median),
+                pos2(-(digit_width / 2.0) + (segment_thickness / 2.0) + segment_spacing,                                                                       digit_median),
+                pos2(-(digit_width / 2.0) + (segment_thickness / 1.0) + segment_spacing,                         (segment_thickness / 2.0)                   + digit_median),
+            ],
+            vec![
+                pos2(                       (segment_thickness / 1.0) + segment_spacing,                         (segment_thickness / 2.0)                   + digit_median),
+                pos2(                                                   segment_spacing,                                                                       digit_median),
+                pos2(                       (segment_thickness / 1.0) + segment_spacing,                       - (segment_thickness / 2.0)                   + digit_median),
+                pos2( (digit_width / 2.0) - (segment_thickness / 1.0) - segment_spacing,                       - (segment_thickness / 2.0)                   + digit_median),
+                pos2( (digit_width / 2.0) - (segment_thickness / 2.0) - segment_spacing,                                                                       digit_median),
+                pos2( (digit_width / 2.0) - (segment_thickness / 1.0) - segment_spacing,                         (segment_thickness / 2.0)                   + digit_median),
+            ],
+            vec![
+                pos2(                                                 - segment_spacing,                                                   - segment_spacing + digit_median),
+                pos2(                     - (segment_thickness / 2.0) - segment_spacing,                       - (segment_thickness / 1.0) - segment_spacing + digit_median),
+                pos2(-(digit_width / 2.0) + (segment_thickness * 1.5) + segment_spacing, -(digit_height / 2.0) + (segment_thickness / 1.0) + segment_spacing               ),
+                pos2(-(digit_width / 2.0) + (segment_thickness / 1.0) + segment_spacing, -(digit_height / 2.0) + (segment_thickness / 1.0) + segment_spacing               ),
+                pos2(-(digit_width / 2.0) + (segment_thickness / 1.0) + segment_spacing, -(digit_height / 2.0) + (segment_thickness * 1.5) + segment_spacing               ),
+                pos2(                     - (segment_thickness / 1.0) - segment_spacing,                       - (segment_thickness / 2.0) - segment_spacing + digit_median),
+            ],
+            vec![
+                pos2(                     - (segment_thickness / 2.0)                  , -(digit_height / 2.0) + (segment_thickness / 1.0) + segment_spacing               ),
+                pos2(                                                               0.0, -(digit_height / 2.0) + (segment_thickness / 1.0) + segment_spacing               ),
+                pos2(                       (segment_thickness / 2.0)                  , -(digit_height / 2.0) + (segment_thickness / 1.0) + segment_spacing               ),
+                pos2(                       (segment_thickness / 2.0)                  ,                       - (segment_thickness / 1.0) - segment_spacing + digit_median),
+                pos2(                                                               0.0,                                                   - segment_spacing + digit_median),
+                pos2(                     - (segment_thickness / 2.0)                  ,                       - (segment_thickness / 1.0) - segment_spacing + digit_median),
+            ],
+            vec![
+                pos2(                       (segment_thickness / 2.0) + segment_spacing,                       - (segment_thickness / 1.0) - segment_spacing + digit_median),
+                pos2( (digit_width / 2.0) - (segment_thickness * 1.5) - segment_spacing, -(digit_height / 2.0) + (segment_thickness / 1.0) + segment_spacing               ),
+                pos2( (digit_width / 2.0) - (segment_thickness / 1.0) - segment_spacing, -(digit_height / 2.0) + (segment_thickness / 1.0) + segment_spacing               ),
+                pos2( (digit_width / 2.0) - (segment_thickness / 1.0) - segment_spacing, -(digit_height / 2.0) + (segment_thickness * 1.5) + segment_spacing               ),
+                pos2(                       (segment_thickness / 1.0) + segment_spacing,                       - (segment_thickness / 2.0) - segment_spacing + digit_median),
+                pos2(                                                   segment_spacing,                                                   - segment_spacing + digit_median),
+            ],
+            vec![
+                pos2(                     - (segment_thickness / 2.0) - segment_spacing,                         (segment_thickness / 1.0) + segment_spacing + digit_median),
+                pos2(-(digit_width / 2.0) + (segment_thickness * 1.5) + segment_spacing,  (digit_height / 2.0) - (segment_thickness / 1.0) - segment_spacing               ),
+                pos2(-(digit_width / 2.0) + (segment_thickness / 1.0) + segment_spacing,  (digit_height / 2.0) - (segment_thickness / 1.0) - segment_spacing               ),
+                pos2(-(digit_width / 2.0) + (segment_thickness / 1.0) + segment_spacing,  (digit_height / 2.0) - (segment_thickness * 1.5) - segment_spacing               ),
+                pos2(                     - (segment_thickness / 1.0) - segment_spacing,                         (segment_thickness / 2.0) + segment_spacing + digit_median),
+                pos2(                                                 - segment_spacing,                                                     segment_spacing + digit_median),
+            ],
+            vec![
+                pos2(                     - (segment_thickness / 2.0)                  ,  (digit_height / 2.0) - (segment_thickness / 1.0) - segment_spacing               ),
+                pos2(                                                               0.0,  (digit_height / 2.0) - (segment_thickness / 1.0) - segment_spacing               ),
+                pos2(                       (segment_thickness / 2.0)                  ,  (digit_height / 2.0) - (segment_thickness / 1.0) - segment_spacing               ),
+                pos2(                       (segment_thickness / 2.0)                  ,                         (segment_thickness / 1.0) + segment_spacing + digit_median),
+                pos2(                                                               0.0,                                                     segment_spacing + digit_median),
+                pos2(                     - (segment_thickness / 2.0)                  ,                         (segment_thickness / 1.0) + segment_spacing + digit_median),
+            ],
+            vec![
+                pos2(                                                   segment_spacing,                                                     segment_spacing + digit_median),
+                pos2(                       (segment_thickness / 2.0) + segment_spacing,                         (segment_thickness / 1.0) + segment_spacing + digit_median),
+                pos2( (digit_width / 2.0) - (segment_thickness * 1.5) - segment_spacing,  (digit_height / 2.0) - (segment_thickness / 1.0) - segment_spacing               ),
+                pos2( (digit_width / 2.0) - (segment_thickness / 1.0) - segment_spacing,  (digit_height / 2.0) - (segment_thickness / 1.0) - segment_spacing               ),
+                pos2( (digit_width / 2.0) - (segment_thickness / 1.0) - segment_spacing,  (digit_height / 2.0) - (segment_thickness * 1.5) - segment_spacing               ),
+                pos2(                       (segment_thickness / 1.0) + segment_spacing,                         (segment_thickness / 2.0) + segment_spacing + digit_median),
+            ],
+        ]
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments(names: &str) -> DisplayGlyph {
+        // H, K, L, N: top left, top right, bottom left, bottom right diagonal
+        // J, M: top and bottom vertical
+        const SEGMENT_NAMES: [&str; 14] = [
+            "A", "B", "C", "D", "E", "F", "G1", "G2", "H", "J", "K", "L", "M", "N",
+        ];
+
+        names
+            .split_whitespace()
+            .map(|name| SEGMENT_NAMES.iter().position(|n| *n == name).unwrap())
+            .fold(0, |glyph, bit| glyph | (1 << bit))
+    }
+
+    #[test]
+    fn digits() {
+        let expected = [
+            ('0', "A B C D E F K L"),
+            ('1', "B C K"),
+            ('2', "A B D E G1 G2"),
+            ('3', "A B C D G1 G2"),
+            ('4', "B C F G1 G2"),
+            ('5', "A C D F G1 G2"),
+            ('6', "A C D E F G1 G2"),
+            ('7', "A B C"),
+            ('8', "A B C D E F G1 G2"),
+            ('9', "A B C D F G1 G2"),
+        ];
+
+        for (c, names) in expected {
+            assert_eq!(FourteenSegment.glyph(c), Some(segments(names)), "{c:?}");
+        }
+    }
+
+    #[test]
+    fn inner_segments() {
+        assert_eq!(FourteenSegment.glyph('/'), Some(segments("K L")));
+        assert_eq!(FourteenSegment.glyph('\\'), Some(segments("H N")));
+        assert_eq!(FourteenSegment.glyph('|'), Some(segments("J M")));
+        assert_eq!(FourteenSegment.glyph('X'), Some(segments("H K L N")));
+    }
+
+    #[test]
+    fn lowercase() {
+        assert_eq!(
+            FourteenSegment.glyph('a'),
+            Some(segments("A B C D E G1 G2"))
+        );
+        assert_eq!(FourteenSegment.glyph('c'), Some(segments("D E G1")));
+        assert_eq!(FourteenSegment.glyph('o'), Some(segments("D E G1 M")));
+    }
+
+    #[test]
+    fn glyphs_fit_segment_count() {
+        for c in ' '..='~' {
+            let glyph = FourteenSegment.glyph(c).unwrap();
+            assert_eq!(glyph >> FourteenSegment.segment_count(), 0, "{c:?}");
+        }
+    }
+}
@@ -1,27 +1,148 @@
+mod display_font;
 mod display_metrics;
+mod editor;
+mod number_format;
 mod widget;
 
+mod fourteen_segment;
 mod nine_segment;
 mod seven_segment;
 mod sixteen_segment;
 
+use std::sync::Arc;
+
 use strum::{Display, EnumIter};
 
+pub use display_font::{DisplayFont, DisplayFontError};
 pub use display_metrics::{DisplayMetrics, DisplayMetricsPreset};
+pub use editor::SegmentedDisplayEditor;
+pub use fourteen_segment::FourteenSegment;
+pub use nine_segment::NineSegment;
+pub use number_format::{NumberFormat, NumberOverflow, NumberRadix};
+pub use seven_segment::SevenSegment;
+pub use sixteen_segment::SixteenSegment;
 pub use widget::SegmentedDisplayWidget;
 
 use egui::Pos2;
 
+use crate::displays::DisplayStyle;
+
 // ----------------------------------------------------------------------------
 
-pub type DisplayGlyph = u16;
+/// Segment bitmask, bit `n` drives segment `n` of the display geometry.
+pub type DisplayGlyph = u64;
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug)]
 pub struct DisplayDigit {
     pub glyph: DisplayGlyph,
     pub dot: bool,
     pub colon: bool,
     pub apostrophe: bool,
+    pub blink: DisplayBlink,
+
+    /// Overrides the widget style for this digit.
+    pub style: Option<DisplayStyle>,
+
+    /// Brightness of the lit segments, blended between the inactive (`0.0`)
+    /// and active (`1.0`) foreground colors.
+    pub intensity: f32,
+}
+
+impl Default for DisplayDigit {
+    fn default() -> Self {
+        Self {
+            glyph: 0,
+            dot: false,
+            colon: false,
+            apostrophe: false,
+            blink: DisplayBlink::NONE,
+            style: None,
+            intensity: 1.0,
+        }
+    }
+}
+
+impl DisplayDigit {
+    /// The digit as shown during the off phase of blinking.
+    #[must_use]
+    pub fn blinked_off(&self) -> Self {
+        Self {
+            glyph: if self.blink.glyph { 0 } else { self.glyph },
+            dot: self.dot && !self.blink.dot,
+            colon: self.colon && !self.blink.colon,
+            apostrophe: self.apostrophe && !self.blink.apostrophe,
+            ..*self
+        }
+    }
+}
+
+/// Parts of a digit that flash at the widget's blink rate.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DisplayBlink {
+    pub glyph: bool,
+    pub dot: bool,
+    pub colon: bool,
+    pub apostrophe: bool,
+}
+
+impl DisplayBlink {
+    pub const NONE: Self = Self {
+        glyph: false,
+        dot: false,
+        colon: false,
+        apostrophe: false,
+    };
+
+    pub const ALL: Self = Self {
+        glyph: true,
+        dot: true,
+        colon: true,
+        apostrophe: true,
+    };
+
+    pub const COLON: Self = Self {
+        colon: true,
+        ..Self::NONE
+    };
+
+    #[must_use]
+    pub fn is_none(&self) -> bool {
+        *self == Self::NONE
+    }
+
+    /// Parts blinking in either `self` or `other`.
+    #[must_use]
+    pub fn union(&self, other: Self) -> Self {
+        Self {
+            glyph: self.glyph || other.glyph,
+            dot: self.dot || other.dot,
+            colon: self.colon || other.colon,
+            apostrophe: self.apostrophe || other.apostrophe,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// How digits longer than the visible digit count are animated.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Display, EnumIter, Eq, PartialEq)]
+pub enum ScrollMode {
+    /// Excess digits are cut off.
+    #[strum(to_string = "Static")]
+    Static,
+
+    /// Text wraps around with a blank digit between repetitions.
+    #[strum(to_string = "Scroll")]
+    Scroll,
+
+    /// Text slides back and forth between its two ends.
+    #[strum(to_string = "Bounce")]
+    Bounce,
+
+    /// Text enters from the right and leaves on the left, even when it fits.
+    #[strum(to_string = "Marquee")]
+    Marquee,
 }
 
 // ----------------------------------------------------------------------------
@@ -35,17 +156,21 @@ pub enum DisplayKind {
     #[strum(to_string = "9-segment")]
     NineSegment,
 
+    #[strum(to_string = "14-segment")]
+    FourteenSegment,
+
     #[strum(to_string = "16-segment")]
     SixteenSegment,
 }
 
 impl DisplayKind {
     #[must_use]
-    pub(crate) fn display_impl(&self) -> Box<dyn DisplayImpl> {
+    pub fn display_impl(&self) -> Arc<dyn DisplayImpl> {
         match *self {
-            DisplayKind::SevenSegment => Box::new(seven_segment::SevenSegment),
-            DisplayKind::NineSegment => Box::new(nine_segment::NineSegment),
-            DisplayKind::SixteenSegment => Box::new(sixteen_segment::SixteenSegment),
+            DisplayKind::SevenSegment => Arc::new(SevenSegment),
+            DisplayKind::NineSegment => Arc::new(NineSegment),
+            DisplayKind::FourteenSegment => Arc::new(FourteenSegment),
+            DisplayKind::SixteenSegment => Arc::new(SixteenSegment),
         }
     }
 
@@ -57,11 +182,21 @@ impl DisplayKind {
 
 // ----------------------------------------------------------------------------
 
-pub(crate) trait DisplayImpl {
+/// Segment layout and glyph table of a display digit.
+///
+/// Implement this trait to plug custom layouts into
+/// [`SegmentedDisplayWidget::with_display_impl`].
+pub trait DisplayImpl: Send + Sync {
+    /// Number of segments, at most `DisplayGlyph::BITS`. Segments beyond that
+    /// are not drawn.
     fn segment_count(&self) -> usize;
 
     fn glyph(&self, c: char) -> Option<DisplayGlyph>;
 
+    /// Returns one outline per segment, in glyph bit order. Coordinates are
+    /// relative to the digit center with the Y axis pointing down; shearing is
+    /// applied by the widget afterwards. `digit_median` is the vertical offset
+    /// of the middle segments from the center.
     fn geometry(
         &self,
         digit_width: f32,
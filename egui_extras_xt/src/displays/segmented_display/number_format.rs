@@ -0,0 +1,261 @@
+use strum::{Display, EnumIter};
+
+// ----------------------------------------------------------------------------
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Display, EnumIter, Eq, PartialEq)]
+pub enum NumberRadix {
+    #[strum(to_string = "Binary")]
+    Binary,
+
+    #[strum(to_string = "Octal")]
+    Octal,
+
+    #[strum(to_string = "Decimal")]
+    Decimal,
+
+    #[strum(to_string = "Hexadecimal")]
+    Hexadecimal,
+}
+
+impl NumberRadix {
+    #[must_use]
+    pub fn radix(&self) -> u32 {
+        match *self {
+            NumberRadix::Binary => 2,
+            NumberRadix::Octal => 8,
+            NumberRadix::Decimal => 10,
+            NumberRadix::Hexadecimal => 16,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// What to show when a number does not fit in the digit count.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Display, EnumIter, Eq, PartialEq)]
+pub enum NumberOverflow {
+    /// All digits show `-`.
+    #[strum(to_string = "Dashes")]
+    Dashes,
+
+    /// A single right-aligned `E`.
+    #[strum(to_string = "Error")]
+    Error,
+
+    /// Only the least significant digits are shown, like an odometer.
+    #[strum(to_string = "Truncate")]
+    Truncate,
+}
+
+// ----------------------------------------------------------------------------
+
+/// Right-aligned, fixed-width number layout for
+/// [`SegmentedDisplayWidget::integer`](crate::displays::SegmentedDisplayWidget::integer)
+/// and [`SegmentedDisplayWidget::decimal`](crate::displays::SegmentedDisplayWidget::decimal).
+///
+/// The decimal point is drawn as the dot of a digit, so the number always
+/// occupies exactly `digit_count` digits.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NumberFormat {
+    pub digit_count: usize,
+    pub decimals: usize,
+    pub radix: NumberRadix,
+    pub leading_zeros: bool,
+    pub overflow: NumberOverflow,
+}
+
+impl NumberFormat {
+    #[must_use]
+    pub fn new(digit_count: usize) -> Self {
+        Self {
+            digit_count,
+            decimals: 0,
+            radix: NumberRadix::Decimal,
+            leading_zeros: false,
+            overflow: NumberOverflow::Dashes,
+        }
+    }
+
+    /// Number of digits after the decimal point.
+    #[must_use]
+    pub fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    #[must_use]
+    pub fn radix(mut self, radix: NumberRadix) -> Self {
+        self.radix = radix;
+        self
+    }
+
+    /// Pad with zeros instead of blanks.
+    #[must_use]
+    pub fn leading_zeros(mut self, leading_zeros: bool) -> Self {
+        self.leading_zeros = leading_zeros;
+        self
+    }
+
+    #[must_use]
+    pub fn overflow(mut self, overflow: NumberOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Lays out a fixed-point number, `mantissa` scaled by `radix^decimals`.
+    /// Returns one `(character, dot)` pair per digit.
+    pub(crate) fn layout_fixed(&self, mantissa: i128) -> Vec<(char, bool)> {
+        if self.digit_count == 0 {
+            return Vec::new();
+        }
+
+        let radix = self.radix.radix();
+        let negative = mantissa < 0;
+
+        let mut magnitude = mantissa.unsigned_abs();
+        let mut digits = Vec::new();
+        while (magnitude > 0) || (digits.len() <= self.decimals) {
+            let digit = (magnitude % radix as u128) as u32;
+            digits.push(char::from_digit(digit, radix).unwrap().to_ascii_uppercase());
+            magnitude /= radix as u128;
+        }
+
+        let sign_width = usize::from(negative);
+        let pad_width = self.digit_count.saturating_sub(digits.len() + sign_width);
+
+        if self.leading_zeros {
+            digits.extend(std::iter::repeat('0').take(pad_width));
+        }
+
+        if digits.len() + sign_width > self.digit_count {
+            match self.overflow {
+                NumberOverflow::Dashes => return vec![('-', false); self.digit_count],
+                NumberOverflow::Error => return self.layout_overflow_error(),
+                NumberOverflow::Truncate => digits.truncate(self.digit_count - sign_width),
+            }
+        }
+
+        if negative {
+            digits.push('-');
+        }
+
+        digits.extend(std::iter::repeat(' ').take(self.digit_count - digits.len()));
+
+        digits
+            .into_iter()
+            .enumerate()
+            .rev()
+            .map(|(index, c)| (c, (self.decimals > 0) && (index == self.decimals)))
+            .collect()
+    }
+
+    /// Rounds `value` to the configured decimals and lays it out.
+    pub(crate) fn layout_float(&self, value: f64) -> Vec<(char, bool)> {
+        let scale = (self.radix.radix() as f64).powi(self.decimals as i32);
+        let mantissa = (value * scale).round();
+
+        if mantissa.is_finite() && (mantissa.abs() < i128::MAX as f64) {
+            self.layout_fixed(mantissa as i128)
+        } else {
+            match self.overflow {
+                NumberOverflow::Dashes | NumberOverflow::Truncate => {
+                    vec![('-', false); self.digit_count]
+                }
+                NumberOverflow::Error => self.layout_overflow_error(),
+            }
+        }
+    }
+
+    fn layout_overflow_error(&self) -> Vec<(char, bool)> {
+        let mut digits = vec![(' ', false); self.digit_count];
+        if let Some(last) = digits.last_mut() {
+            *last = ('E', false);
+        }
+        digits
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Renders a layout as text, dots following their digit.
+    fn text(layout: Vec<(char, bool)>) -> String {
+        layout
+            .into_iter()
+            .flat_map(|(c, dot)| [Some(c), dot.then_some('.')])
+            .flatten()
+            .collect()
+    }
+
+    #[test]
+    fn layout_fixed() {
+        let format = NumberFormat::new(4);
+
+        assert_eq!(text(format.layout_fixed(42)), "  42");
+        assert_eq!(text(format.layout_fixed(0)), "   0");
+        assert_eq!(text(format.layout_fixed(-5)), "  -5");
+        assert_eq!(text(format.leading_zeros(true).layout_fixed(-5)), "-005");
+        assert_eq!(text(format.decimals(2).layout_fixed(1234)), "12.34");
+        assert_eq!(text(format.decimals(2).layout_fixed(5)), " 0.05");
+        assert_eq!(text(format.decimals(2).layout_fixed(-5)), "-0.05");
+        assert_eq!(
+            text(format.radix(NumberRadix::Hexadecimal).layout_fixed(255)),
+            "  FF"
+        );
+        assert_eq!(
+            text(format.radix(NumberRadix::Binary).layout_fixed(5)),
+            " 101"
+        );
+        assert!(NumberFormat::new(0).layout_fixed(1).is_empty());
+    }
+
+    #[test]
+    fn layout_overflow() {
+        let format = NumberFormat::new(4);
+
+        assert_eq!(text(format.layout_fixed(12345)), "----");
+        assert_eq!(text(format.layout_fixed(-1234)), "----");
+        assert_eq!(
+            text(format.overflow(NumberOverflow::Error).layout_fixed(12345)),
+            "   E"
+        );
+        assert_eq!(
+            text(
+                format
+                    .overflow(NumberOverflow::Truncate)
+                    .layout_fixed(12345)
+            ),
+            "2345"
+        );
+        assert_eq!(
+            text(
+                format
+                    .overflow(NumberOverflow::Truncate)
+                    .layout_fixed(-12345)
+            ),
+            "-345"
+        );
+    }
+
+    #[test]
+    fn layout_float() {
+        let format = NumberFormat::new(5).decimals(2);
+
+        assert_eq!(text(format.layout_float(12.345_6)), " 12.35");
+        assert_eq!(text(format.layout_float(-0.125)), " -0.13");
+        assert_eq!(text(format.layout_float(f64::NAN)), "-----");
+        assert_eq!(
+            text(
+                format
+                    .overflow(NumberOverflow::Error)
+                    .layout_float(f64::INFINITY)
+            ),
+            "    E"
+        );
+    }
+}
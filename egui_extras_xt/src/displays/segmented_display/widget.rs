@@ -1,34 +1,75 @@
-use egui::{pos2, vec2, Pos2, Response, Sense, Shape, Stroke, Ui, Widget};
+use std::sync::Arc;
+use std::time::Duration;
+
+use egui::{pos2, vec2, Id, Pos2, Rect, Response, Sense, Stroke, Ui, UiBuilder, Vec2, Widget};
 use itertools::Itertools;
 
 use crate::displays::segmented_display::{
-    DisplayDigit, DisplayKind, DisplayMetrics, DisplayMetricsPreset,
+    DisplayBlink, DisplayDigit, DisplayFont, DisplayGlyph, DisplayImpl, DisplayKind,
+    DisplayMetrics, DisplayMetricsPreset, NumberFormat, ScrollMode,
 };
-use crate::displays::{DisplayStyle, DisplayStylePreset};
+use crate::displays::{DisplayStyle, DisplayStylePreset, ThemedDisplayStyle};
+use crate::polygon::concave_polygon;
+
+/// Character a pushed digit was made from. Glyphs are looked up when the
+/// widget is painted, so the font may be set after the digits are pushed.
+#[derive(Clone, Copy, Debug)]
+enum DigitSource {
+    /// Glyph given by the caller.
+    Glyph,
+
+    /// Text character, skipped when no glyph is found.
+    Text(char),
+
+    /// Number layout character, blank when no glyph is found.
+    Number(char),
+}
 
 #[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
 pub struct SegmentedDisplayWidget {
-    display_kind: DisplayKind,
-    digits: Vec<DisplayDigit>,
+    display_impl: Arc<dyn DisplayImpl>,
+    digits: Vec<(DigitSource, DisplayDigit)>,
     digit_height: f32,
     metrics: DisplayMetrics,
-    style: DisplayStyle,
+    style: ThemedDisplayStyle,
     show_dots: bool,
     show_colons: bool,
     show_apostrophes: bool,
+    font: Option<Arc<DisplayFont>>,
+    digit_count: Option<usize>,
+    scroll_mode: ScrollMode,
+    scroll_speed: f32,
+    blink: DisplayBlink,
+    blink_rate: f32,
+    digit_style: Option<DisplayStyle>,
+    digit_intensity: f32,
 }
 
 impl SegmentedDisplayWidget {
     pub fn new(display_kind: DisplayKind) -> Self {
+        Self::with_display_impl(display_kind.display_impl())
+    }
+
+    pub fn with_display_impl(display_impl: Arc<dyn DisplayImpl>) -> Self {
+        debug_assert!(display_impl.segment_count() <= DisplayGlyph::BITS as usize);
+
         Self {
-            display_kind,
+            display_impl,
             digits: Vec::new(),
             digit_height: 80.0,
             metrics: DisplayMetrics::default(),
-            style: DisplayStylePreset::Default.style(),
+            style: ThemedDisplayStyle::default(),
             show_dots: true,
             show_colons: true,
             show_apostrophes: true,
+            font: None,
+            digit_count: None,
+            scroll_mode: ScrollMode::Static,
+            scroll_speed: 4.0,
+            blink: DisplayBlink::NONE,
+            blink_rate: 1.0,
+            digit_style: None,
+            digit_intensity: 1.0,
         }
     }
 
@@ -40,37 +81,122 @@ impl SegmentedDisplayWidget {
         Self::new(DisplayKind::NineSegment).push_string(value.as_ref())
     }
 
+    pub fn fourteen_segment<T: AsRef<str>>(value: T) -> Self {
+        Self::new(DisplayKind::FourteenSegment).push_string(value.as_ref())
+    }
+
     pub fn sixteen_segment<T: AsRef<str>>(value: T) -> Self {
         Self::new(DisplayKind::SixteenSegment).push_string(value.as_ref())
     }
 
+    /// Right-aligned integer. With non-zero `format.decimals`, `value` is
+    /// treated as fixed-point, e.g. `1234` with two decimals shows `12.34`.
+    pub fn integer(display_kind: DisplayKind, value: i64, format: NumberFormat) -> Self {
+        Self::new(display_kind).push_integer(value, format)
+    }
+
+    /// Right-aligned number rounded to `format.decimals` decimals.
+    pub fn decimal(display_kind: DisplayKind, value: f64, format: NumberFormat) -> Self {
+        Self::new(display_kind).push_decimal(value, format)
+    }
+
+    fn glyph(&self, c: char) -> Option<DisplayGlyph> {
+        match self.font {
+            Some(ref font) if !font.inherits_builtin() => font.glyph(c),
+            Some(ref font) => font.glyph(c).or_else(|| self.display_impl.glyph(c)),
+            None => self.display_impl.glyph(c),
+        }
+    }
+
+    /// Glyph of a pushed digit with the current font, `None` for skipped text
+    /// characters.
+    fn resolve_glyph(&self, source: DigitSource, digit: &DisplayDigit) -> Option<DisplayGlyph> {
+        match source {
+            DigitSource::Glyph => Some(digit.glyph),
+            DigitSource::Text(c) => self.glyph(c),
+            DigitSource::Number(c) => Some(self.glyph(c).unwrap_or_default()),
+        }
+    }
+
+    /// Pushed digits as they are displayed.
+    fn resolved_digits(&self) -> Vec<DisplayDigit> {
+        self.digits
+            .iter()
+            .filter_map(|(source, digit)| {
+                self.resolve_glyph(*source, digit)
+                    .map(|glyph| DisplayDigit {
+                        glyph,
+                        blink: digit.blink.union(self.blink),
+                        style: digit.style.or(self.digit_style),
+                        intensity: digit.intensity * self.digit_intensity,
+                        ..*digit
+                    })
+            })
+            .collect_vec()
+    }
+
     pub fn push_string<T: AsRef<str>>(mut self, value: T) -> Self {
-        let display_impl = self.display_kind.display_impl();
-
-        self.digits.extend(
-            [None]
-                .into_iter()
-                .chain(value.as_ref().chars().map(Some))
-                .chain([None])
-                .tuple_windows()
-                .filter_map(|(prev, curr, next)| match curr {
-                    Some('.') if self.show_dots => None,
-                    Some(':') if self.show_colons => None,
-                    Some('\'') if self.show_apostrophes => None,
-                    Some(c) if display_impl.glyph(c).is_some() => Some(DisplayDigit {
-                        glyph: display_impl.glyph(c).unwrap(),
+        let digits = [None]
+            .into_iter()
+            .chain(value.as_ref().chars().map(Some))
+            .chain([None])
+            .tuple_windows()
+            .filter_map(|(prev, curr, next)| match curr {
+                Some('.') if self.show_dots => None,
+                Some(':') if self.show_colons => None,
+                Some('\'') if self.show_apostrophes => None,
+                Some(c) => Some((
+                    DigitSource::Text(c),
+                    DisplayDigit {
                         dot: (next == Some('.')) && self.show_dots,
                         colon: (prev == Some(':')) && self.show_colons,
                         apostrophe: (prev == Some('\'')) && self.show_apostrophes,
-                    }),
-                    _ => None,
-                }),
-        );
+                        ..Default::default()
+                    },
+                )),
+                _ => None,
+            })
+            .collect_vec();
+
+        self.digits.extend(digits);
+        self
+    }
+
+    pub fn push_integer(self, value: i64, format: NumberFormat) -> Self {
+        let layout = format.layout_fixed(value.into());
+        self.push_layout(layout)
+    }
+
+    pub fn push_decimal(self, value: f64, format: NumberFormat) -> Self {
+        let layout = format.layout_float(value);
+        self.push_layout(layout)
+    }
+
+    pub(crate) fn push_layout(mut self, layout: Vec<(char, bool)>) -> Self {
+        let digits = layout
+            .into_iter()
+            .map(|(c, dot)| {
+                (
+                    DigitSource::Number(c),
+                    DisplayDigit {
+                        dot: dot && self.show_dots,
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect_vec();
+
+        self.digits.extend(digits);
+        self
+    }
+
+    pub(crate) fn clear_digits(mut self) -> Self {
+        self.digits.clear();
         self
     }
 
     pub fn push_digit(mut self, digit: DisplayDigit) -> Self {
-        self.digits.push(digit);
+        self.digits.push((DigitSource::Glyph, digit));
         self
     }
 
@@ -80,12 +206,19 @@ impl SegmentedDisplayWidget {
     }
 
     pub fn style(mut self, style: DisplayStyle) -> Self {
-        self.style = style;
+        self.style = ThemedDisplayStyle::Fixed(style);
         self
     }
 
     pub fn style_preset(mut self, preset: DisplayStylePreset) -> Self {
-        self.style = preset.style();
+        self.style = ThemedDisplayStyle::Preset(preset);
+        self
+    }
+
+    /// Uses the style registered under `name` in the
+    /// [`DisplayStyleRegistry`](crate::displays::DisplayStyleRegistry).
+    pub fn style_name(mut self, name: impl Into<String>) -> Self {
+        self.style = ThemedDisplayStyle::Named(name.into());
         self
     }
 
@@ -113,36 +246,209 @@ impl SegmentedDisplayWidget {
         self.show_apostrophes = show_apostrophes;
         self
     }
+
+    /// Custom glyph table consulted before the built-in one.
+    pub fn font(mut self, font: impl Into<Arc<DisplayFont>>) -> Self {
+        self.font = Some(font.into());
+        self
+    }
+
+    /// Fixed number of visible digits. Defaults to the number of pushed digits.
+    pub fn digit_count(mut self, digit_count: usize) -> Self {
+        self.digit_count = Some(digit_count);
+        self
+    }
+
+    pub fn scroll_mode(mut self, scroll_mode: ScrollMode) -> Self {
+        self.scroll_mode = scroll_mode;
+        self
+    }
+
+    /// Scrolling speed in digits per second.
+    pub fn scroll_speed(mut self, scroll_speed: impl Into<f32>) -> Self {
+        self.scroll_speed = scroll_speed.into();
+        self
+    }
+
+    /// Blinking parts of all digits, in addition to the ones set per digit.
+    pub fn blink(mut self, blink: DisplayBlink) -> Self {
+        self.blink = blink;
+        self
+    }
+
+    /// Style override for the digits without a style of their own.
+    pub fn digit_style(mut self, digit_style: Option<DisplayStyle>) -> Self {
+        self.digit_style = digit_style;
+        self
+    }
+
+    /// Brightness of the digits, from `0.0` to `1.0`. Scales the intensity set
+    /// per digit.
+    pub fn digit_intensity(mut self, digit_intensity: impl Into<f32>) -> Self {
+        self.digit_intensity = digit_intensity.into();
+        self
+    }
+
+    /// Modifies the pushed digits in place, e.g. to highlight a single one.
+    /// Digits are passed with the glyphs of the current font; changing the
+    /// glyph of a digit detaches it from the font.
+    pub fn map_digits(mut self, mut f: impl FnMut(usize, &mut DisplayDigit)) -> Self {
+        let mut digits = std::mem::take(&mut self.digits);
+        let mut digit_index = 0;
+
+        for (source, digit) in &mut digits {
+            let Some(glyph) = self.resolve_glyph(*source, digit) else {
+                continue;
+            };
+
+            digit.glyph = glyph;
+            f(digit_index, digit);
+            digit_index += 1;
+
+            if digit.glyph != glyph {
+                *source = DigitSource::Glyph;
+            }
+        }
+
+        self.digits = digits;
+        self
+    }
+
+    /// Blinking frequency in Hz.
+    pub fn blink_rate(mut self, blink_rate: impl Into<f32>) -> Self {
+        self.blink_rate = blink_rate.into();
+        self
+    }
+
+    fn visible_digits<'d>(
+        &self,
+        ui: &Ui,
+        digits: &'d [DisplayDigit],
+    ) -> Vec<Option<&'d DisplayDigit>> {
+        let digit_total = digits.len();
+        let digit_count = self.digit_count.unwrap_or(digit_total);
+
+        let scroll_length = match self.scroll_mode {
+            ScrollMode::Static => 0,
+            ScrollMode::Scroll | ScrollMode::Bounce if digit_total <= digit_count => 0,
+            ScrollMode::Scroll => digit_total + 1,
+            ScrollMode::Bounce => 2 * (digit_total - digit_count),
+            ScrollMode::Marquee => digit_total + digit_count,
+        };
+
+        let scroll_step = if (scroll_length > 0) && (self.scroll_speed > 0.0) {
+            let scroll_time = ui.input(|input| input.time) * self.scroll_speed as f64;
+            ui.ctx().request_repaint_after(Duration::from_secs_f64(
+                (1.0 - scroll_time.fract()) / self.scroll_speed as f64,
+            ));
+            (scroll_time as usize) % scroll_length
+        } else {
+            0
+        };
+
+        (0..digit_count)
+            .map(|digit_index| match self.scroll_mode {
+                ScrollMode::Scroll if scroll_length > 0 => {
+                    digits.get((scroll_step + digit_index) % scroll_length)
+                }
+                ScrollMode::Bounce if scroll_length > 0 => {
+                    let offset = scroll_step.min(scroll_length - scroll_step);
+                    digits.get(offset + digit_index)
+                }
+                ScrollMode::Marquee => (scroll_step + digit_index)
+                    .checked_sub(digit_count)
+                    .and_then(|index| digits.get(index)),
+                _ => digits.get(digit_index),
+            })
+            .collect()
+    }
+
+    fn blink_phase(&self, ui: &Ui, digits: &[DisplayDigit]) -> bool {
+        if (self.blink_rate <= 0.0) || digits.iter().all(|digit| digit.blink.is_none()) {
+            return true;
+        }
+
+        let blink_time = ui.input(|input| input.time) * self.blink_rate as f64 * 2.0;
+        ui.ctx().request_repaint_after(Duration::from_secs_f64(
+            (1.0 - blink_time.fract()) / (self.blink_rate as f64 * 2.0),
+        ));
+        (blink_time as u64) % 2 == 0
+    }
 }
 
-impl Widget for SegmentedDisplayWidget {
-    fn ui(self, ui: &mut Ui) -> Response {
-        let display_impl = self.display_kind.display_impl();
+impl SegmentedDisplayWidget {
+    fn digit_width(&self) -> f32 {
+        self.digit_height * self.metrics.digit_ratio
+    }
+
+    pub(crate) fn visible_digit_count(&self) -> usize {
+        self.digit_count.unwrap_or_else(|| {
+            self.digits
+                .iter()
+                .filter(|(source, digit)| self.resolve_glyph(*source, digit).is_some())
+                .count()
+        })
+    }
+
+    pub(crate) fn desired_size(&self) -> Vec2 {
+        let digit_count = self.visible_digit_count();
+        let digit_width = self.digit_width();
+
+        vec2(
+            (digit_width * digit_count as f32)
+                + (self.metrics.digit_spacing
+                    * digit_width
+                    * (digit_count.saturating_sub(1)) as f32)
+                + (2.0 * self.metrics.margin_horizontal * digit_width)
+                + (2.0 * (self.metrics.digit_shearing * digit_width).abs()),
+            self.digit_height + (2.0 * self.metrics.margin_vertical * self.digit_height),
+        )
+    }
+
+    pub(crate) fn digit_center(&self, rect: Rect, digit_index: usize) -> Pos2 {
+        let digit_width = self.digit_width();
+
+        rect.left_center()
+            + vec2(
+                (self.metrics.margin_horizontal * digit_width)
+                    + (self.metrics.digit_shearing * digit_width).abs()
+                    + ((digit_width + self.metrics.digit_spacing * digit_width)
+                        * digit_index as f32)
+                    + (digit_width / 2.0),
+                0.0,
+            )
+    }
+
+    /// Index of the visible digit under `pos`, including the spacing around it.
+    pub(crate) fn digit_index_at(&self, rect: Rect, pos: Pos2) -> Option<usize> {
+        let digit_pitch = self.digit_width() * (1.0 + self.metrics.digit_spacing);
+
+        (0..self.visible_digit_count()).find(|&digit_index| {
+            let digit_center = self.digit_center(rect, digit_index);
+            rect.contains(pos) && ((pos.x - digit_center.x).abs() <= (digit_pitch / 2.0))
+        })
+    }
+
+    pub(crate) fn paint(&self, ui: &mut Ui, rect: Rect, id: Id) {
+        let display_impl = &self.display_impl;
 
         let digit_height = self.digit_height;
-        let digit_width = digit_height * self.metrics.digit_ratio;
+        let digit_width = self.digit_width();
 
         // Turn relative metrics to absolute metrics
         let segment_thickness = self.metrics.segment_thickness * digit_height;
         let segment_spacing = self.metrics.segment_spacing * digit_height;
         let digit_shearing = self.metrics.digit_shearing * digit_width;
         let digit_spacing = self.metrics.digit_spacing * digit_width;
-        let margin_horizontal = self.metrics.margin_horizontal * digit_width;
-        let margin_vertical = self.metrics.margin_vertical * digit_height;
         let digit_median = self.metrics.digit_median * (digit_height / 2.0);
         let colon_separation = self.metrics.colon_separation * (digit_height / 2.0);
 
-        let desired_size = vec2(
-            (digit_width * self.digits.len() as f32)
-                + (digit_spacing * (self.digits.len().saturating_sub(1)) as f32)
-                + (2.0 * margin_horizontal)
-                + (2.0 * digit_shearing.abs()),
-            digit_height + (2.0 * margin_vertical),
-        );
-
-        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::click());
+        let display_style = self.style.resolve(ui);
+        let digits = self.resolved_digits();
+        let visible_digits = self.visible_digits(ui, &digits);
+        let blink_phase = self.blink_phase(ui, &digits);
 
-        let mut child_ui = ui.child_ui(rect, *ui.layout(), None);
+        let mut child_ui = ui.new_child(UiBuilder::new().max_rect(rect).layout(*ui.layout()));
         child_ui.set_clip_rect(child_ui.clip_rect().intersect(rect));
 
         if child_ui.is_rect_visible(rect) {
@@ -150,7 +456,7 @@ impl Widget for SegmentedDisplayWidget {
             ui.painter().rect(
                 rect,
                 ui.style().visuals.noninteractive().rounding,
-                self.style.background_color,
+                display_style.background_color,
                 Stroke::NONE,
             );
 
@@ -161,7 +467,10 @@ impl Widget for SegmentedDisplayWidget {
                 segment_spacing,
                 digit_median,
             );
-            assert_eq!(segment_geometry.len(), display_impl.segment_count());
+            debug_assert_eq!(segment_geometry.len(), display_impl.segment_count());
+
+            // Outlines beyond the glyph bits have nothing to drive them
+            let segment_count = segment_geometry.len().min(DisplayGlyph::BITS as usize);
 
             #[rustfmt::skip]
             let apostrophe_points: Vec<Pos2> = vec![
@@ -177,72 +486,279 @@ impl Widget for SegmentedDisplayWidget {
                 pos2( (digit_width / 2.0) + (digit_spacing / 2.0), (digit_height / 2.0) - (segment_thickness / 2.0))
             );
 
-            let paint_digit = |digit: &DisplayDigit, digit_center: Pos2| {
-                let transform = |&Pos2 { x, y }| {
+            let transform = |digit_center: Pos2| {
+                move |&Pos2 { x, y }: &Pos2| {
                     digit_center + vec2(x, y)
                         - vec2((y / (digit_height / 2.0)) * digit_shearing, 0.0)
+                }
+            };
+
+            // Segments followed by the dot, colon and apostrophe
+            let part_count = segment_count + 3;
+            let part_active = |digit: &DisplayDigit, part_index: usize| match part_index {
+                _ if part_index < segment_count => ((digit.glyph >> part_index) & 0x01) != 0x00,
+                _ if part_index == part_count - 3 => digit.dot,
+                _ if part_index == part_count - 2 => digit.colon,
+                _ => digit.apostrophe,
+            };
+
+            let time = ui.input(|input| input.time);
+            let afterglow_enabled = std::iter::once(&display_style)
+                .chain(digits.iter().filter_map(|digit| digit.style.as_ref()))
+                .any(|style| style.effects.persistence > 0.0);
+
+            // Time and level of the last moment each part was lit
+            let mut afterglow = if afterglow_enabled {
+                ui.memory(|memory| memory.data.get_temp::<Vec<(f64, f32)>>(id))
+                    .filter(|afterglow| afterglow.len() == visible_digits.len() * part_count)
+                    .unwrap_or_else(|| {
+                        vec![(f64::NEG_INFINITY, 0.0); visible_digits.len() * part_count]
+                    })
+            } else {
+                Vec::new()
+            };
+            let mut afterglow_fading = false;
+
+            let digit_levels = visible_digits
+                .iter()
+                .enumerate()
+                .map(|(digit_index, digit)| {
+                    let digit = match digit {
+                        Some(digit) if blink_phase => **digit,
+                        Some(digit) => digit.blinked_off(),
+                        None => DisplayDigit::default(),
+                    };
+                    let style = digit.style.unwrap_or(display_style);
+
+                    let levels = (0..part_count)
+                        .map(|part_index| {
+                            let level = if part_active(&digit, part_index) {
+                                digit.intensity
+                            } else {
+                                0.0
+                            };
+
+                            let Some((lit_time, lit_level)) =
+                                afterglow.get_mut(digit_index * part_count + part_index)
+                            else {
+                                return level;
+                            };
+
+                            if level > 0.0 {
+                                (*lit_time, *lit_level) = (time, level);
+                                return level;
+                            }
+
+                            let persistence = style.effects.persistence as f64;
+                            let fade = if persistence > 0.0 {
+                                (1.0 - (time - *lit_time) / persistence).clamp(0.0, 1.0) as f32
+                            } else {
+                                0.0
+                            };
+
+                            afterglow_fading |= fade > 0.0;
+                            *lit_level * fade
+                        })
+                        .collect_vec();
+
+                    (style, levels)
+                })
+                .collect_vec();
+
+            if afterglow_enabled {
+                ui.memory_mut(|memory| memory.data.insert_temp(id, afterglow));
+            }
+
+            if afterglow_fading {
+                ui.ctx().request_repaint();
+            }
+
+            let painter = child_ui.painter();
+
+            let paint_digit = |digit_index: usize, glow_pass: bool| {
+                let (style, ref levels) = digit_levels[digit_index];
+                let transform = transform(self.digit_center(rect, digit_index));
+
+                let paint_polygon = |points: &[Pos2], level: f32| {
+                    let points = points.iter().map(transform).collect_vec();
+
+                    if glow_pass {
+                        style.paint_glow(painter, &points, level);
+                    } else {
+                        painter.add(concave_polygon(
+                            painter,
+                            points,
+                            style.foreground_color_blend(level),
+                            style.foreground_stroke_blend(level),
+                        ));
+                    }
                 };
 
-                for (segment_index, segment_points) in segment_geometry.iter().enumerate() {
-                    let segment_active = ((digit.glyph >> segment_index) & 0x01) != 0x00;
+                let paint_circle = |center: &Pos2, level: f32| {
+                    if glow_pass {
+                        style.paint_glow_circle(
+                            painter,
+                            transform(center),
+                            segment_thickness / 2.0,
+                            level,
+                        );
+                    } else {
+                        painter.circle(
+                            transform(center),
+                            segment_thickness / 2.0,
+                            style.foreground_color_blend(level),
+                            style.foreground_stroke_blend(level),
+                        );
+                    }
+                };
 
-                    // TODO: concave_polygon
-                    // https://github.com/emilk/egui/issues/513
-                    child_ui.painter().add(Shape::convex_polygon(
-                        segment_points.iter().map(transform).collect_vec(),
-                        self.style.foreground_color(segment_active),
-                        self.style.foreground_stroke(segment_active),
-                    ));
+                for (segment_points, level) in segment_geometry.iter().zip(&levels[..segment_count])
+                {
+                    paint_polygon(segment_points, *level);
                 }
 
                 if self.show_dots {
-                    child_ui.painter().circle(
-                        transform(&dot_pos),
-                        segment_thickness / 2.0,
-                        self.style.foreground_color(digit.dot),
-                        self.style.foreground_stroke(digit.dot),
-                    );
+                    paint_circle(&dot_pos, levels[part_count - 3]);
                 }
 
                 if self.show_colons {
-                    child_ui.painter().circle(
-                        transform(&colon_top_pos),
-                        segment_thickness / 2.0,
-                        self.style.foreground_color(digit.colon),
-                        self.style.foreground_stroke(digit.colon),
-                    );
-
-                    child_ui.painter().circle(
-                        transform(&colon_bottom_pos),
-                        segment_thickness / 2.0,
-                        self.style.foreground_color(digit.colon),
-                        self.style.foreground_stroke(digit.colon),
-                    );
+                    paint_circle(&colon_top_pos, levels[part_count - 2]);
+                    paint_circle(&colon_bottom_pos, levels[part_count - 2]);
                 }
 
                 if self.show_apostrophes {
-                    child_ui.painter().add(Shape::convex_polygon(
-                        apostrophe_points.iter().map(transform).collect_vec(),
-                        self.style.foreground_color(digit.apostrophe),
-                        self.style.foreground_stroke(digit.apostrophe),
-                    ));
+                    paint_polygon(&apostrophe_points, levels[part_count - 1]);
                 }
             };
 
-            for (digit_index, digit) in self.digits.iter().enumerate() {
-                let digit_center = rect.left_center()
-                    + vec2(
-                        margin_horizontal
-                            + digit_shearing.abs()
-                            + ((digit_width + digit_spacing) * digit_index as f32)
-                            + (digit_width / 2.0),
-                        0.0,
-                    );
-
-                paint_digit(digit, digit_center);
+            for digit_index in 0..digit_levels.len() {
+                paint_digit(digit_index, true);
             }
+
+            for digit_index in 0..digit_levels.len() {
+                paint_digit(digit_index, false);
+            }
+
+            // Draw the glass reflection without clipping
+            display_style.paint_reflection(
+                ui.painter(),
+                rect,
+                ui.style().visuals.noninteractive().rounding,
+            );
         }
+    }
+}
 
+impl Widget for SegmentedDisplayWidget {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let (rect, response) = ui.allocate_exact_size(self.desired_size(), Sense::click());
+        self.paint(ui, rect, response.id);
         response
     }
 }
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn font_applies_to_digits_pushed_before_it() {
+        let font = DisplayFont::new()
+            .with_glyph('Γ', 0x0031)
+            .with_glyph('Π', 0x0037);
+
+        let glyphs = |widget: &SegmentedDisplayWidget| {
+            widget
+                .resolved_digits()
+                .iter()
+                .map(|digit| digit.glyph)
+                .collect_vec()
+        };
+
+        let widget = SegmentedDisplayWidget::seven_segment("ΓΠ").font(font.clone());
+        assert_eq!(glyphs(&widget), [0x0031, 0x0037]);
+
+        let widget = SegmentedDisplayWidget::new(DisplayKind::SevenSegment)
+            .font(font)
+            .push_string("ΓΠ");
+        assert_eq!(glyphs(&widget), [0x0031, 0x0037]);
+    }
+
+    #[test]
+    fn blink_applies_to_digits_pushed_before_it() {
+        let widget = SegmentedDisplayWidget::seven_segment("12:34")
+            .map_digits(|digit_index, digit| {
+                if digit_index == 0 {
+                    digit.blink = DisplayBlink::ALL;
+                }
+            })
+            .blink(DisplayBlink::COLON);
+
+        let blinks = widget
+            .resolved_digits()
+            .iter()
+            .map(|digit| digit.blink)
+            .collect_vec();
+        assert_eq!(
+            blinks,
+            [
+                DisplayBlink::ALL,
+                DisplayBlink::COLON,
+                DisplayBlink::COLON,
+                DisplayBlink::COLON
+            ]
+        );
+    }
+
+    #[test]
+    fn digit_style_and_intensity_apply_to_digits_pushed_before_them() {
+        let amber = DisplayStylePreset::Amber.style();
+        let calculator = DisplayStylePreset::Calculator.style();
+
+        let widget = SegmentedDisplayWidget::seven_segment("12")
+            .map_digits(|digit_index, digit| {
+                if digit_index == 0 {
+                    digit.style = Some(calculator);
+                    digit.intensity = 0.5;
+                }
+            })
+            .digit_style(Some(amber))
+            .digit_intensity(0.5);
+
+        let background_color =
+            |digit: &DisplayDigit| digit.style.map(|style| style.background_color);
+
+        let digits = widget.resolved_digits();
+        assert_eq!(
+            background_color(&digits[0]),
+            Some(calculator.background_color)
+        );
+        assert_eq!(digits[0].intensity, 0.25);
+        assert_eq!(background_color(&digits[1]), Some(amber.background_color));
+        assert_eq!(digits[1].intensity, 0.5);
+    }
+
+    #[test]
+    fn map_digits_detaches_changed_glyphs() {
+        let widget = SegmentedDisplayWidget::seven_segment("12")
+            .map_digits(|digit_index, digit| {
+                if digit_index == 1 {
+                    digit.glyph = 0x0040;
+                }
+            })
+            .font(
+                DisplayFont::new()
+                    .with_glyph('1', 0x0001)
+                    .with_glyph('2', 0x0002),
+            );
+
+        let glyphs = widget
+            .resolved_digits()
+            .iter()
+            .map(|digit| digit.glyph)
+            .collect_vec();
+        assert_eq!(glyphs, [0x0001, 0x0040]);
+    }
+}
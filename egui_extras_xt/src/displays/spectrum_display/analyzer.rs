@@ -0,0 +1,389 @@
+use std::ops::RangeInclusive;
+
+use egui::{
+    pos2, remap_clamp, vec2, Align2, Color32, FontId, FontSelection, Id, Rect, Response, Sense,
+    Shape, Stroke, Ui, Widget,
+};
+use itertools::Itertools;
+use strum::{Display, EnumIter};
+
+use crate::displays::peak_hold::PeakHold;
+use crate::displays::spectrum_display::fft::{Spectrum, WindowFunction};
+use crate::displays::spectrum_display::{
+    format_frequency, fraction_to_frequency, frequency_grid, frequency_to_fraction,
+};
+use crate::displays::waveform_display::{grid_lines, nice_step, BufferLayout, SampleRange};
+
+// ----------------------------------------------------------------------------
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Display, EnumIter, Eq, PartialEq)]
+pub enum SpectrumStyle {
+    /// Logarithmically spaced bands, like a graphic equalizer.
+    #[strum(to_string = "Bars")]
+    Bars,
+
+    #[strum(to_string = "Line")]
+    Line,
+}
+
+// ----------------------------------------------------------------------------
+
+/// Frequency spectrum of the most recent samples, on a logarithmic frequency
+/// axis and a dBFS level axis.
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct SpectrumAnalyzerWidget<'a, SampleType>
+where
+    SampleType: SampleRange<SampleType>,
+{
+    buffer: &'a [SampleType],
+    buffer_layout: BufferLayout,
+    channels: usize,
+    channel: Option<usize>,
+    sample_rate: f32,
+    fft_size: usize,
+    window_function: WindowFunction,
+    width: f32,
+    height: f32,
+    frequency_range: RangeInclusive<f32>,
+    db_range: RangeInclusive<f32>,
+    style: SpectrumStyle,
+    bar_count: usize,
+    color: Option<Color32>,
+    peak_hold: bool,
+    peak_hold_time: f32,
+    peak_decay_rate: f32,
+    show_graticule: bool,
+}
+
+impl<'a, SampleType> SpectrumAnalyzerWidget<'a, SampleType>
+where
+    SampleType: SampleRange<SampleType>,
+{
+    pub fn new(buffer: &'a [SampleType]) -> Self {
+        Self {
+            buffer,
+            buffer_layout: BufferLayout::Interleaved,
+            channels: 1,
+            channel: None,
+            sample_rate: 44100.0,
+            fft_size: 2048,
+            window_function: WindowFunction::Hann,
+            width: 512.0,
+            height: 160.0,
+            frequency_range: 20.0..=20000.0,
+            db_range: -90.0..=0.0,
+            style: SpectrumStyle::Bars,
+            bar_count: 32,
+            color: None,
+            peak_hold: true,
+            peak_hold_time: 1.0,
+            peak_decay_rate: 30.0,
+            show_graticule: true,
+        }
+    }
+
+    pub fn buffer_layout(mut self, buffer_layout: BufferLayout) -> Self {
+        self.buffer_layout = buffer_layout;
+        self
+    }
+
+    pub fn channels(mut self, channels: usize) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    /// Analyzed channel. Defaults to the average of all channels.
+    pub fn channel(mut self, channel: usize) -> Self {
+        self.channel = Some(channel);
+        self
+    }
+
+    /// Samples per second, for the frequency axis.
+    pub fn sample_rate(mut self, sample_rate: f32) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// Number of most recent samples analyzed, rounded up to a power of two.
+    /// Shorter buffers are zero-padded.
+    pub fn fft_size(mut self, fft_size: usize) -> Self {
+        self.fft_size = fft_size.next_power_of_two();
+        self
+    }
+
+    pub fn window_function(mut self, window_function: WindowFunction) -> Self {
+        self.window_function = window_function;
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<f32>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<f32>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    /// Displayed frequencies in hertz, clamped to the Nyquist frequency.
+    pub fn frequency_range(mut self, frequency_range: RangeInclusive<f32>) -> Self {
+        self.frequency_range = frequency_range;
+        self
+    }
+
+    /// Displayed levels in dBFS.
+    pub fn db_range(mut self, db_range: RangeInclusive<f32>) -> Self {
+        self.db_range = db_range;
+        self
+    }
+
+    pub fn style(mut self, style: SpectrumStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Number of bands in [`SpectrumStyle::Bars`].
+    pub fn bar_count(mut self, bar_count: usize) -> Self {
+        self.bar_count = bar_count.max(1);
+        self
+    }
+
+    pub fn color(mut self, color: Color32) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn peak_hold(mut self, peak_hold: bool) -> Self {
+        self.peak_hold = peak_hold;
+        self
+    }
+
+    /// Time the peak indicator stays put before decaying, in seconds.
+    pub fn peak_hold_time(mut self, peak_hold_time: impl Into<f32>) -> Self {
+        self.peak_hold_time = peak_hold_time.into();
+        self
+    }
+
+    /// Fall speed of the peak indicator, in decibels per second.
+    pub fn peak_decay_rate(mut self, peak_decay_rate: impl Into<f32>) -> Self {
+        self.peak_decay_rate = peak_decay_rate.into();
+        self
+    }
+
+    pub fn show_graticule(mut self, show_graticule: bool) -> Self {
+        self.show_graticule = show_graticule;
+        self
+    }
+
+    /// Holds the highest level of each band, and lets it decay once the hold
+    /// time has passed.
+    fn update_peaks(&self, ui: &Ui, id: Id, levels: &[f32]) -> Vec<f32> {
+        let time = ui.input(|input| input.time);
+
+        // Per band peak levels in dBFS
+        let mut state = ui
+            .memory(|memory| memory.data.get_temp::<Vec<PeakHold>>(id))
+            .filter(|state| state.len() == levels.len())
+            .unwrap_or_else(|| {
+                levels
+                    .iter()
+                    .map(|&level| PeakHold::new(level, time))
+                    .collect_vec()
+            });
+
+        let mut decaying = false;
+
+        let peaks = state
+            .iter_mut()
+            .zip(levels)
+            .map(|(peak_hold, &level)| {
+                let peak = peak_hold.update(level, time, self.peak_hold_time, self.peak_decay_rate);
+                decaying |= (peak > level)
+                    && (self.peak_decay_rate > 0.0)
+                    && (peak > *self.db_range.start());
+                peak
+            })
+            .collect_vec();
+
+        ui.memory_mut(|memory| memory.data.insert_temp(id, state));
+
+        if decaying {
+            ui.ctx().request_repaint();
+        }
+
+        peaks
+    }
+}
+
+impl<'a, SampleType> Widget for SpectrumAnalyzerWidget<'a, SampleType>
+where
+    SampleType: SampleRange<SampleType> + Into<f32> + Copy,
+{
+    fn ui(self, ui: &mut Ui) -> Response {
+        let desired_size = vec2(self.width, self.height);
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        let plot_rect = rect.shrink(4.0);
+
+        let frequency_range = {
+            let nyquist = self.sample_rate / 2.0;
+            let high = self.frequency_range.end().min(nyquist);
+            self.frequency_range.start().max(1.0).min(high * 0.5)..=high
+        };
+
+        let spectrum = Spectrum::analyze(
+            self.buffer,
+            self.buffer_layout,
+            self.channels,
+            self.channel,
+            self.sample_rate,
+            self.fft_size,
+            self.window_function,
+        );
+
+        // Bands as fractions of the frequency axis
+        let band_count = match self.style {
+            SpectrumStyle::Bars => self.bar_count,
+            SpectrumStyle::Line => (plot_rect.width() / 2.0).ceil().max(1.0) as usize,
+        };
+
+        let bands = (0..band_count)
+            .map(|index| {
+                (index as f32 / band_count as f32)..=((index + 1) as f32 / band_count as f32)
+            })
+            .collect_vec();
+
+        let levels = bands
+            .iter()
+            .map(|band| {
+                spectrum.band_level(
+                    fraction_to_frequency(*band.start(), &frequency_range)
+                        ..=fraction_to_frequency(*band.end(), &frequency_range),
+                )
+            })
+            .collect_vec();
+
+        let peaks = self
+            .peak_hold
+            .then(|| self.update_peaks(ui, response.id, &levels));
+
+        if ui.is_rect_visible(rect) {
+            let visuals = *ui.style().noninteractive();
+
+            let font_id = FontSelection::Default.resolve(ui.style());
+            let label_font_id = FontId::new(font_id.size * 0.75, font_id.family.clone());
+            let label_color = ui.style().visuals.weak_text_color();
+            let grid_color = visuals.bg_stroke.color;
+            let color = self.color.unwrap_or(visuals.fg_stroke.color);
+            let peak_color = ui.style().visuals.warn_fg_color;
+
+            ui.painter().rect(
+                rect,
+                visuals.rounding,
+                ui.style().visuals.extreme_bg_color,
+                visuals.fg_stroke,
+            );
+
+            let painter = ui.painter_at(plot_rect);
+
+            let fraction_to_x =
+                |fraction: f32| remap_clamp(fraction, 0.0..=1.0, plot_rect.x_range());
+            let level_to_y =
+                |level: f32| remap_clamp(level, self.db_range.clone(), plot_rect.bottom_up_range());
+
+            // Graticule
+            if self.show_graticule {
+                let mut last_label_x = f32::NEG_INFINITY;
+
+                for frequency in frequency_grid(&frequency_range) {
+                    let x = fraction_to_x(frequency_to_fraction(frequency, &frequency_range));
+                    painter.vline(x, plot_rect.y_range(), Stroke::new(1.0, grid_color));
+
+                    if x - last_label_x > label_font_id.size * 2.5 {
+                        painter.text(
+                            pos2(x + 2.0, plot_rect.bottom()),
+                            Align2::LEFT_BOTTOM,
+                            format_frequency(frequency),
+                            label_font_id.clone(),
+                            label_color,
+                        );
+                        last_label_x = x;
+                    }
+                }
+
+                let db_span = self.db_range.end() - self.db_range.start();
+                let db_step = nice_step(db_span, plot_rect.height() / (font_id.size * 2.0));
+
+                for level in grid_lines(&self.db_range, db_step) {
+                    let y = level_to_y(level);
+                    painter.hline(plot_rect.x_range(), y, Stroke::new(1.0, grid_color));
+
+                    painter.text(
+                        pos2(plot_rect.left() + 2.0, y),
+                        Align2::LEFT_TOP,
+                        format!("{level} dB"),
+                        label_font_id.clone(),
+                        label_color,
+                    );
+                }
+            }
+
+            // Spectrum
+            match self.style {
+                SpectrumStyle::Bars => {
+                    for (band, level) in bands.iter().zip(&levels) {
+                        let x_range = fraction_to_x(*band.start())..=fraction_to_x(*band.end());
+                        let gap = if (x_range.end() - x_range.start()) > 3.0 {
+                            1.0
+                        } else {
+                            0.0
+                        };
+
+                        painter.rect_filled(
+                            Rect::from_x_y_ranges(
+                                (x_range.start() + gap)..=(x_range.end() - gap),
+                                level_to_y(*level)..=plot_rect.bottom(),
+                            ),
+                            0.0,
+                            color,
+                        );
+                    }
+
+                    for (band, peak) in bands.iter().zip(peaks.iter().flatten()) {
+                        if peak > self.db_range.start() {
+                            painter.hline(
+                                fraction_to_x(*band.start())..=fraction_to_x(*band.end()),
+                                level_to_y(*peak),
+                                Stroke::new(2.0, peak_color),
+                            );
+                        }
+                    }
+                }
+                SpectrumStyle::Line => {
+                    let to_points = |levels: &[f32]| {
+                        bands
+                            .iter()
+                            .zip(levels)
+                            .map(|(band, level)| {
+                                pos2(
+                                    fraction_to_x((band.start() + band.end()) / 2.0),
+                                    level_to_y(*level),
+                                )
+                            })
+                            .collect_vec()
+                    };
+
+                    if let Some(peaks) = &peaks {
+                        painter.add(Shape::line(to_points(peaks), Stroke::new(1.0, peak_color)));
+                    }
+
+                    painter.add(Shape::line(to_points(&levels), Stroke::new(1.5, color)));
+                }
+            }
+        }
+
+        response
+    }
+}
@@ -0,0 +1,390 @@
+use std::f32::consts::TAU;
+use std::ops::RangeInclusive;
+
+use egui::remap;
+use itertools::Itertools;
+use strum::{Display, EnumIter};
+
+use crate::displays::waveform_display::{BufferLayout, ChannelSamples, SampleRange};
+
+// ----------------------------------------------------------------------------
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Display, EnumIter, Eq, PartialEq)]
+pub enum WindowFunction {
+    /// No windowing. Narrowest peaks, but the most leakage.
+    #[strum(to_string = "Rectangular")]
+    Rectangular,
+
+    #[strum(to_string = "Hann")]
+    Hann,
+
+    #[strum(to_string = "Hamming")]
+    Hamming,
+
+    #[strum(to_string = "Blackman")]
+    Blackman,
+
+    /// Wide peaks with accurate amplitudes.
+    #[strum(to_string = "Flat top")]
+    FlatTop,
+}
+
+impl WindowFunction {
+    /// Coefficients of the generalized cosine window, alternating in sign.
+    fn cosine_terms(&self) -> &'static [f32] {
+        match *self {
+            WindowFunction::Rectangular => &[1.0],
+            WindowFunction::Hann => &[0.5, 0.5],
+            WindowFunction::Hamming => &[0.54, 0.46],
+            WindowFunction::Blackman => &[0.42, 0.5, 0.08],
+            WindowFunction::FlatTop => &[
+                0.215_578_95,
+                0.416_631_58,
+                0.277_263_16,
+                0.083_578_95,
+                0.006_947_37,
+            ],
+        }
+    }
+
+    /// Periodic window of `size` samples.
+    pub(crate) fn coefficients(&self, size: usize) -> Vec<f32> {
+        let terms = self.cosine_terms();
+
+        (0..size)
+            .map(|index| {
+                let phase = TAU * index as f32 / size as f32;
+
+                terms
+                    .iter()
+                    .enumerate()
+                    .map(|(k, term)| {
+                        let sign = if k % 2 == 0 { 1.0 } else { -1.0 };
+                        sign * term * (k as f32 * phase).cos()
+                    })
+                    .sum()
+            })
+            .collect_vec()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// In-place iterative radix-2 FFT. Both slices must have the same power of
+/// two length.
+fn fft(re: &mut [f32], im: &mut [f32]) {
+    let size = re.len();
+    assert!(size.is_power_of_two());
+    assert_eq!(im.len(), size);
+
+    if size < 2 {
+        return;
+    }
+
+    let bits = size.trailing_zeros();
+
+    for index in 0..size {
+        let reversed = index.reverse_bits() >> (usize::BITS - bits);
+
+        if reversed > index {
+            re.swap(index, reversed);
+            im.swap(index, reversed);
+        }
+    }
+
+    let mut length = 2;
+
+    while length <= size {
+        let half = length / 2;
+
+        for k in 0..half {
+            let (sin, cos) = (-TAU * k as f32 / length as f32).sin_cos();
+
+            for start in (0..size).step_by(length) {
+                let (a, b) = (start + k, start + k + half);
+
+                let t_re = re[b] * cos - im[b] * sin;
+                let t_im = re[b] * sin + im[b] * cos;
+
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+            }
+        }
+
+        length *= 2;
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Magnitude spectrum of the most recent `fft_size` samples of a buffer,
+/// scaled so that a full-scale sine reads 0 dBFS.
+pub(crate) struct Spectrum {
+    magnitudes: Vec<f32>,
+    bin_width: f32,
+}
+
+impl Spectrum {
+    /// Analyzes `channel`, or the average of all channels if `None`. Buffers
+    /// shorter than `fft_size` are windowed as they are, then zero-padded.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn analyze<T>(
+        buffer: &[T],
+        buffer_layout: BufferLayout,
+        channels: usize,
+        channel: Option<usize>,
+        sample_rate: f32,
+        fft_size: usize,
+        window_function: WindowFunction,
+    ) -> Self
+    where
+        T: SampleRange<T> + Into<f32> + Copy,
+    {
+        assert_eq!(buffer.len() % channels, 0);
+        assert!(fft_size.is_power_of_two());
+        assert!(channel.map_or(true, |channel_id| channel_id < channels));
+
+        let channel_samples = match channel {
+            Some(channel_id) => vec![ChannelSamples::new(
+                buffer,
+                buffer_layout,
+                channels,
+                channel_id,
+            )],
+            None => (0..channels)
+                .map(|channel_id| ChannelSamples::new(buffer, buffer_layout, channels, channel_id))
+                .collect_vec(),
+        };
+
+        let channel_length = buffer.len() / channels;
+        let length = channel_length.min(fft_size);
+        let start = channel_length - length;
+
+        let window = window_function.coefficients(length);
+        let normalize = |sample: f32| remap(sample, T::DISPLAY_RANGE, -1.0..=1.0);
+
+        let mut re = vec![0.0; fft_size];
+        let mut im = vec![0.0; fft_size];
+
+        for (offset, index) in (start..channel_length).enumerate() {
+            let sample = channel_samples
+                .iter()
+                .map(|samples| normalize(samples.get(index)))
+                .sum::<f32>()
+                / channel_samples.len() as f32;
+
+            re[offset] = sample * window[offset];
+        }
+
+        fft(&mut re, &mut im);
+
+        let scale = 2.0 / window.iter().sum::<f32>().max(f32::EPSILON);
+
+        let magnitudes = re
+            .iter()
+            .zip(&im)
+            .take(fft_size / 2 + 1)
+            .map(|(re, im)| re.hypot(*im) * scale)
+            .collect_vec();
+
+        Self {
+            magnitudes,
+            bin_width: sample_rate / fft_size as f32,
+        }
+    }
+
+    /// Highest level in `frequencies`, in dBFS. Bands narrower than a bin
+    /// are interpolated between the neighbouring bins.
+    pub(crate) fn band_level(&self, frequencies: RangeInclusive<f32>) -> f32 {
+        let last_bin = self.magnitudes.len() - 1;
+
+        let low_bin = (frequencies.start() / self.bin_width).max(0.0);
+        let high_bin = (frequencies.end() / self.bin_width).min(last_bin as f32);
+
+        let first = low_bin.ceil() as usize;
+        let last = high_bin.floor() as usize;
+
+        let magnitude = if first <= last {
+            self.magnitudes[first..=last]
+                .iter()
+                .copied()
+                .fold(0.0, f32::max)
+        } else {
+            let center = (low_bin + high_bin) / 2.0;
+            let index = (center.floor() as usize).min(last_bin.saturating_sub(1));
+            let fraction = (center - index as f32).clamp(0.0, 1.0);
+
+            let next = self.magnitudes[(index + 1).min(last_bin)];
+            self.magnitudes[index] + (next - self.magnitudes[index]) * fraction
+        };
+
+        20.0 * magnitude.max(1e-10).log10()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use super::*;
+
+    fn assert_approx_eq(a: f32, b: f32, tolerance: f32) {
+        assert!((a - b).abs() <= tolerance, "{a} != {b}");
+    }
+
+    #[test]
+    fn window_coefficients() {
+        let rectangular = WindowFunction::Rectangular.coefficients(8);
+        assert!(rectangular.iter().all(|&coefficient| coefficient == 1.0));
+
+        let hann = WindowFunction::Hann.coefficients(8);
+        assert_approx_eq(hann[0], 0.0, 1e-6);
+        assert_approx_eq(hann[4], 1.0, 1e-6);
+
+        for window_function in [
+            WindowFunction::Hann,
+            WindowFunction::Hamming,
+            WindowFunction::Blackman,
+            WindowFunction::FlatTop,
+        ] {
+            let window = window_function.coefficients(64);
+            assert_approx_eq(window[32], window.iter().copied().fold(0.0, f32::max), 1e-6);
+
+            // Periodic windows are symmetric around the middle sample
+            for index in 1..32 {
+                assert_approx_eq(window[index], window[64 - index], 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn fft_matches_dft() {
+        let input = (0..16)
+            .map(|index| ((index * 7) % 5) as f32 - 2.0)
+            .collect_vec();
+
+        let mut re = input.clone();
+        let mut im = vec![0.0; input.len()];
+        fft(&mut re, &mut im);
+
+        for k in 0..input.len() {
+            let (dft_re, dft_im) =
+                input
+                    .iter()
+                    .enumerate()
+                    .fold((0.0, 0.0), |(dft_re, dft_im), (n, sample)| {
+                        let (sin, cos) = (-TAU * (k * n) as f32 / input.len() as f32).sin_cos();
+                        (dft_re + sample * cos, dft_im + sample * sin)
+                    });
+
+            assert_approx_eq(re[k], dft_re, 1e-4);
+            assert_approx_eq(im[k], dft_im, 1e-4);
+        }
+    }
+
+    #[test]
+    fn fft_of_impulse_is_flat() {
+        let mut re = vec![0.0; 32];
+        let mut im = vec![0.0; 32];
+        re[0] = 1.0;
+
+        fft(&mut re, &mut im);
+
+        assert!(re.iter().all(|&value| (value - 1.0).abs() < 1e-6));
+        assert!(im.iter().all(|&value| value.abs() < 1e-6));
+    }
+
+    #[test]
+    fn full_scale_sine_reads_0_dbfs() {
+        let (sample_rate, fft_size) = (8192.0, 1024);
+        let frequency = 100.0 * sample_rate / fft_size as f32; // Centre of bin 100
+
+        let buffer = (0..fft_size)
+            .map(|index| (2.0 * PI * frequency * index as f32 / sample_rate).sin())
+            .collect_vec();
+
+        for window_function in [
+            WindowFunction::Rectangular,
+            WindowFunction::Hann,
+            WindowFunction::Blackman,
+            WindowFunction::FlatTop,
+        ] {
+            let spectrum = Spectrum::analyze(
+                &buffer,
+                BufferLayout::Interleaved,
+                1,
+                None,
+                sample_rate,
+                fft_size,
+                window_function,
+            );
+
+            assert_approx_eq(spectrum.band_level(frequency..=frequency), 0.0, 0.1);
+            assert!(
+                spectrum.band_level(2000.0..=3000.0) < -60.0,
+                "{window_function}"
+            );
+        }
+    }
+
+    #[test]
+    fn channel_selection() {
+        let (sample_rate, fft_size) = (1024.0, 256);
+        let frequency = 64.0;
+
+        // Left channel: full-scale sine, right channel: silence
+        let buffer = (0..fft_size)
+            .flat_map(|index| {
+                let sample = (2.0 * PI * frequency * index as f32 / sample_rate).sin();
+                [sample, 0.0]
+            })
+            .collect_vec();
+
+        let level = |channel| {
+            Spectrum::analyze(
+                &buffer,
+                BufferLayout::Interleaved,
+                2,
+                channel,
+                sample_rate,
+                fft_size,
+                WindowFunction::Hann,
+            )
+            .band_level(frequency..=frequency)
+        };
+
+        assert_approx_eq(level(Some(0)), 0.0, 0.1);
+        assert!(level(Some(1)) < -100.0);
+        assert_approx_eq(level(None), 20.0 * 0.5_f32.log10(), 0.1);
+    }
+
+    #[test]
+    fn band_level() {
+        let spectrum = Spectrum {
+            magnitudes: vec![0.1, 1.0, 0.5, 0.01],
+            bin_width: 10.0,
+        };
+
+        assert_approx_eq(spectrum.band_level(5.0..=25.0), 0.0, 1e-5);
+        assert_approx_eq(
+            spectrum.band_level(15.0..=100.0),
+            20.0 * 0.5_f32.log10(),
+            1e-5,
+        );
+
+        // Narrower than a bin, interpolated between bins 1 and 2
+        assert_approx_eq(
+            spectrum.band_level(14.0..=16.0),
+            20.0 * 0.75_f32.log10(),
+            1e-5,
+        );
+
+        // Outside of the spectrum
+        assert_approx_eq(spectrum.band_level(100.0..=200.0), -40.0, 1e-5);
+    }
+}
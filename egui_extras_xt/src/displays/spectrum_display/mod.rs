@@ -0,0 +1,40 @@
+mod analyzer;
+mod fft;
+mod spectrogram;
+
+use std::ops::RangeInclusive;
+
+pub use analyzer::{SpectrumAnalyzerWidget, SpectrumStyle};
+pub use fft::WindowFunction;
+pub use spectrogram::{SpectrogramColormap, SpectrogramOrientation, SpectrogramWidget};
+
+// ----------------------------------------------------------------------------
+
+/// Position of `frequency` on a logarithmic axis spanning `range`, from `0.0`
+/// to `1.0`.
+pub(crate) fn frequency_to_fraction(frequency: f32, range: &RangeInclusive<f32>) -> f32 {
+    (frequency / range.start()).ln() / (range.end() / range.start()).ln()
+}
+
+pub(crate) fn fraction_to_frequency(fraction: f32, range: &RangeInclusive<f32>) -> f32 {
+    range.start() * (range.end() / range.start()).powf(fraction)
+}
+
+/// Grid lines at 1, 2 and 5 times each power of ten inside `range`.
+pub(crate) fn frequency_grid(range: &RangeInclusive<f32>) -> impl Iterator<Item = f32> + '_ {
+    let first_decade = range.start().log10().floor() as i32;
+    let last_decade = range.end().log10().ceil() as i32;
+
+    (first_decade..=last_decade)
+        .flat_map(|decade| [1.0, 2.0, 5.0].map(|mantissa| mantissa * 10.0_f32.powi(decade)))
+        .filter(|frequency| range.contains(frequency))
+}
+
+/// Formats a frequency in hertz, switching to kilohertz from 1000 Hz.
+pub(crate) fn format_frequency(frequency: f32) -> String {
+    if frequency >= 1000.0 {
+        format!("{}k", frequency / 1000.0)
+    } else {
+        format!("{frequency}")
+    }
+}
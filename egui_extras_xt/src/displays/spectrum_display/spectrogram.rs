@@ -0,0 +1,447 @@
+use std::ops::RangeInclusive;
+
+use egui::epaint::Vertex;
+use egui::{
+    pos2, remap_clamp, vec2, Align2, Color32, ColorImage, FontId, FontSelection, Mesh, Pos2, Rect,
+    Response, Sense, Stroke, TextureHandle, TextureOptions, Ui, Widget,
+};
+use itertools::Itertools;
+use strum::{Display, EnumIter};
+
+use crate::displays::spectrum_display::fft::{Spectrum, WindowFunction};
+use crate::displays::spectrum_display::{
+    format_frequency, fraction_to_frequency, frequency_grid, frequency_to_fraction,
+};
+use crate::displays::waveform_display::{BufferLayout, SampleRange};
+
+// ----------------------------------------------------------------------------
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Display, EnumIter, Eq, PartialEq)]
+pub enum SpectrogramColormap {
+    #[strum(to_string = "Grayscale")]
+    Grayscale,
+
+    #[strum(to_string = "Heat")]
+    Heat,
+
+    #[strum(to_string = "Viridis")]
+    Viridis,
+}
+
+impl SpectrogramColormap {
+    const GRAYSCALE: [Color32; 2] = [Color32::BLACK, Color32::WHITE];
+
+    const HEAT: [Color32; 6] = [
+        Color32::from_rgb(0x00, 0x00, 0x04),
+        Color32::from_rgb(0x42, 0x0a, 0x68),
+        Color32::from_rgb(0x93, 0x26, 0x67),
+        Color32::from_rgb(0xdd, 0x51, 0x3a),
+        Color32::from_rgb(0xfc, 0xa5, 0x0a),
+        Color32::from_rgb(0xfc, 0xff, 0xa4),
+    ];
+
+    const VIRIDIS: [Color32; 5] = [
+        Color32::from_rgb(0x44, 0x01, 0x54),
+        Color32::from_rgb(0x3b, 0x52, 0x8b),
+        Color32::from_rgb(0x21, 0x91, 0x8c),
+        Color32::from_rgb(0x5e, 0xc9, 0x62),
+        Color32::from_rgb(0xfd, 0xe7, 0x25),
+    ];
+
+    fn stops(&self) -> &'static [Color32] {
+        match *self {
+            SpectrogramColormap::Grayscale => &Self::GRAYSCALE,
+            SpectrogramColormap::Heat => &Self::HEAT,
+            SpectrogramColormap::Viridis => &Self::VIRIDIS,
+        }
+    }
+
+    /// Color of `value`, from `0.0` (silence) to `1.0` (full scale).
+    pub(crate) fn color(&self, value: f32) -> Color32 {
+        let stops = self.stops();
+
+        let position = value.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+        let index = (position.floor() as usize).min(stops.len() - 2);
+        let fraction = position - index as f32;
+
+        let (from, to) = (stops[index], stops[index + 1]);
+        let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * fraction).round() as u8;
+
+        Color32::from_rgb(
+            lerp(from.r(), to.r()),
+            lerp(from.g(), to.g()),
+            lerp(from.b(), to.b()),
+        )
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Display, EnumIter, Eq, PartialEq)]
+pub enum SpectrogramOrientation {
+    /// Time on the horizontal axis, newest on the right.
+    #[strum(to_string = "Horizontal")]
+    Horizontal,
+
+    /// Time on the vertical axis, newest at the top (waterfall).
+    #[strum(to_string = "Vertical")]
+    Vertical,
+}
+
+// ----------------------------------------------------------------------------
+
+/// History texture, used as a ring buffer of columns from low to high time
+/// with the highest frequency in the top row.
+#[derive(Clone)]
+struct SpectrogramState {
+    texture: TextureHandle,
+    size: [usize; 2],
+    next_column: usize,
+    last_levels: Vec<f32>,
+}
+
+/// Adds a quad with the texture coordinates given per corner, clockwise from
+/// the top left.
+fn add_quad(mesh: &mut Mesh, positions: [Pos2; 4], uvs: [Pos2; 4]) {
+    let first = mesh.vertices.len() as u32;
+
+    for (pos, uv) in positions.into_iter().zip(uvs) {
+        mesh.vertices.push(Vertex {
+            pos,
+            uv,
+            color: Color32::WHITE,
+        });
+    }
+
+    mesh.add_triangle(first, first + 1, first + 2);
+    mesh.add_triangle(first, first + 2, first + 3);
+}
+
+// ----------------------------------------------------------------------------
+
+/// Scrolling history of the frequency spectrum, one column per new buffer.
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct SpectrogramWidget<'a, SampleType>
+where
+    SampleType: SampleRange<SampleType>,
+{
+    buffer: &'a [SampleType],
+    buffer_layout: BufferLayout,
+    channels: usize,
+    channel: Option<usize>,
+    sample_rate: f32,
+    fft_size: usize,
+    window_function: WindowFunction,
+    width: f32,
+    height: f32,
+    frequency_range: RangeInclusive<f32>,
+    db_range: RangeInclusive<f32>,
+    colormap: SpectrogramColormap,
+    orientation: SpectrogramOrientation,
+    show_labels: bool,
+}
+
+impl<'a, SampleType> SpectrogramWidget<'a, SampleType>
+where
+    SampleType: SampleRange<SampleType>,
+{
+    pub fn new(buffer: &'a [SampleType]) -> Self {
+        Self {
+            buffer,
+            buffer_layout: BufferLayout::Interleaved,
+            channels: 1,
+            channel: None,
+            sample_rate: 44100.0,
+            fft_size: 2048,
+            window_function: WindowFunction::Hann,
+            width: 512.0,
+            height: 160.0,
+            frequency_range: 20.0..=20000.0,
+            db_range: -90.0..=0.0,
+            colormap: SpectrogramColormap::Heat,
+            orientation: SpectrogramOrientation::Horizontal,
+            show_labels: true,
+        }
+    }
+
+    pub fn buffer_layout(mut self, buffer_layout: BufferLayout) -> Self {
+        self.buffer_layout = buffer_layout;
+        self
+    }
+
+    pub fn channels(mut self, channels: usize) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    /// Analyzed channel. Defaults to the average of all channels.
+    pub fn channel(mut self, channel: usize) -> Self {
+        self.channel = Some(channel);
+        self
+    }
+
+    /// Samples per second, for the frequency axis.
+    pub fn sample_rate(mut self, sample_rate: f32) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// Number of most recent samples analyzed, rounded up to a power of two.
+    /// Shorter buffers are zero-padded.
+    pub fn fft_size(mut self, fft_size: usize) -> Self {
+        self.fft_size = fft_size.next_power_of_two();
+        self
+    }
+
+    pub fn window_function(mut self, window_function: WindowFunction) -> Self {
+        self.window_function = window_function;
+        self
+    }
+
+    /// Also the number of columns kept in [`SpectrogramOrientation::Horizontal`].
+    pub fn width(mut self, width: impl Into<f32>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Also the number of rows kept in [`SpectrogramOrientation::Vertical`].
+    pub fn height(mut self, height: impl Into<f32>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    /// Displayed frequencies in hertz, clamped to the Nyquist frequency.
+    pub fn frequency_range(mut self, frequency_range: RangeInclusive<f32>) -> Self {
+        self.frequency_range = frequency_range;
+        self
+    }
+
+    /// Levels in dBFS mapped to the ends of the colormap.
+    pub fn db_range(mut self, db_range: RangeInclusive<f32>) -> Self {
+        self.db_range = db_range;
+        self
+    }
+
+    pub fn colormap(mut self, colormap: SpectrogramColormap) -> Self {
+        self.colormap = colormap;
+        self
+    }
+
+    pub fn orientation(mut self, orientation: SpectrogramOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    pub fn show_labels(mut self, show_labels: bool) -> Self {
+        self.show_labels = show_labels;
+        self
+    }
+}
+
+impl<'a, SampleType> Widget for SpectrogramWidget<'a, SampleType>
+where
+    SampleType: SampleRange<SampleType> + Into<f32> + Copy,
+{
+    fn ui(self, ui: &mut Ui) -> Response {
+        let desired_size = vec2(self.width, self.height);
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        let image_rect = rect.shrink(1.0);
+
+        let frequency_range = {
+            let nyquist = self.sample_rate / 2.0;
+            let high = self.frequency_range.end().min(nyquist);
+            self.frequency_range.start().max(1.0).min(high * 0.5)..=high
+        };
+
+        let (time_extent, frequency_extent) = match self.orientation {
+            SpectrogramOrientation::Horizontal => (image_rect.width(), image_rect.height()),
+            SpectrogramOrientation::Vertical => (image_rect.height(), image_rect.width()),
+        };
+
+        let columns = (time_extent.round() as usize).max(1);
+        let rows = (frequency_extent.round() as usize).max(1);
+
+        let spectrum = Spectrum::analyze(
+            self.buffer,
+            self.buffer_layout,
+            self.channels,
+            self.channel,
+            self.sample_rate,
+            self.fft_size,
+            self.window_function,
+        );
+
+        let levels = (0..rows)
+            .rev()
+            .map(|row| {
+                spectrum.band_level(
+                    fraction_to_frequency(row as f32 / rows as f32, &frequency_range)
+                        ..=fraction_to_frequency((row + 1) as f32 / rows as f32, &frequency_range),
+                )
+            })
+            .collect_vec();
+
+        // Append a column for each new spectrum
+        let mut state = ui
+            .memory(|memory| memory.data.get_temp::<SpectrogramState>(response.id))
+            .filter(|state| state.size == [columns, rows])
+            .unwrap_or_else(|| SpectrogramState {
+                texture: ui.ctx().load_texture(
+                    format!("spectrogram_{:?}", response.id),
+                    ColorImage::new([columns, rows], self.colormap.color(0.0)),
+                    TextureOptions::LINEAR,
+                ),
+                size: [columns, rows],
+                next_column: 0,
+                last_levels: Vec::new(),
+            });
+
+        if state.last_levels != levels {
+            let column = ColorImage {
+                size: [1, rows],
+                pixels: levels
+                    .iter()
+                    .map(|level| {
+                        self.colormap
+                            .color(remap_clamp(*level, self.db_range.clone(), 0.0..=1.0))
+                    })
+                    .collect_vec(),
+            };
+
+            state
+                .texture
+                .set_partial([state.next_column, 0], column, TextureOptions::LINEAR);
+
+            state.next_column = (state.next_column + 1) % columns;
+            state.last_levels = levels;
+        }
+
+        ui.memory_mut(|memory| memory.data.insert_temp(response.id, state.clone()));
+
+        if ui.is_rect_visible(rect) {
+            let visuals = *ui.style().noninteractive();
+
+            ui.painter().rect(
+                rect,
+                visuals.rounding,
+                ui.style().visuals.extreme_bg_color,
+                visuals.fg_stroke,
+            );
+
+            // History, oldest columns first
+            let split = state.next_column as f32 / columns as f32;
+            let mut mesh = Mesh::with_texture(state.texture.id());
+
+            match self.orientation {
+                SpectrogramOrientation::Horizontal => {
+                    let split_x = image_rect.left() + (1.0 - split) * image_rect.width();
+
+                    mesh.add_rect_with_uv(
+                        Rect::from_x_y_ranges(image_rect.left()..=split_x, image_rect.y_range()),
+                        Rect::from_min_max(pos2(split, 0.0), pos2(1.0, 1.0)),
+                        Color32::WHITE,
+                    );
+
+                    mesh.add_rect_with_uv(
+                        Rect::from_x_y_ranges(split_x..=image_rect.right(), image_rect.y_range()),
+                        Rect::from_min_max(pos2(0.0, 0.0), pos2(split, 1.0)),
+                        Color32::WHITE,
+                    );
+                }
+                SpectrogramOrientation::Vertical => {
+                    let split_y = image_rect.top() + split * image_rect.height();
+
+                    for (y_range, top_u, bottom_u) in [
+                        (image_rect.top()..=split_y, split, 0.0),
+                        (split_y..=image_rect.bottom(), 1.0, split),
+                    ] {
+                        let quad = Rect::from_x_y_ranges(image_rect.x_range(), y_range);
+
+                        add_quad(
+                            &mut mesh,
+                            [
+                                quad.left_top(),
+                                quad.right_top(),
+                                quad.right_bottom(),
+                                quad.left_bottom(),
+                            ],
+                            [
+                                pos2(top_u, 1.0),
+                                pos2(top_u, 0.0),
+                                pos2(bottom_u, 0.0),
+                                pos2(bottom_u, 1.0),
+                            ],
+                        );
+                    }
+                }
+            }
+
+            ui.painter().add(mesh);
+
+            // Frequency labels
+            if self.show_labels {
+                let font_id = FontSelection::Default.resolve(ui.style());
+                let label_font_id = FontId::new(font_id.size * 0.75, font_id.family.clone());
+                let label_color = ui.style().visuals.text_color();
+                let label_background = ui.style().visuals.extreme_bg_color.gamma_multiply(0.6);
+
+                let painter = ui.painter_at(image_rect);
+                let mut last_label_position = f32::NEG_INFINITY;
+
+                for frequency in frequency_grid(&frequency_range) {
+                    let fraction = frequency_to_fraction(frequency, &frequency_range);
+
+                    let (tick, anchor, align, position) = match self.orientation {
+                        SpectrogramOrientation::Horizontal => {
+                            let y = remap_clamp(fraction, 0.0..=1.0, image_rect.bottom_up_range());
+                            (
+                                [pos2(image_rect.left(), y), pos2(image_rect.left() + 4.0, y)],
+                                pos2(image_rect.left() + 6.0, y),
+                                Align2::LEFT_CENTER,
+                                -y,
+                            )
+                        }
+                        SpectrogramOrientation::Vertical => {
+                            let x = remap_clamp(fraction, 0.0..=1.0, image_rect.x_range());
+                            (
+                                [
+                                    pos2(x, image_rect.bottom()),
+                                    pos2(x, image_rect.bottom() - 4.0),
+                                ],
+                                pos2(x, image_rect.bottom() - 6.0),
+                                Align2::CENTER_BOTTOM,
+                                x,
+                            )
+                        }
+                    };
+
+                    painter.line_segment(tick, Stroke::new(1.0, label_color));
+
+                    let galley = painter.layout_no_wrap(
+                        format_frequency(frequency),
+                        label_font_id.clone(),
+                        label_color,
+                    );
+
+                    let spacing = match self.orientation {
+                        SpectrogramOrientation::Horizontal => galley.size().y * 1.5,
+                        SpectrogramOrientation::Vertical => galley.size().x * 1.5,
+                    };
+
+                    if position - last_label_position > spacing {
+                        let label_rect = align.anchor_size(anchor, galley.size());
+
+                        painter.rect_filled(label_rect.expand(1.0), 2.0, label_background);
+                        painter.galley(label_rect.min, galley, label_color);
+                        last_label_position = position;
+                    }
+                }
+            }
+        }
+
+        response
+    }
+}
@@ -0,0 +1,97 @@
+mod samples;
+mod trigger;
+mod vectorscope;
+mod view;
+mod widget;
+
+use std::ops::RangeInclusive;
+
+use strum::{Display, EnumIter};
+
+pub use samples::PeakCache;
+pub use trigger::{Trigger, TriggerMode, TriggerSlope, TriggerStatus};
+pub use vectorscope::{VectorscopeMode, VectorscopeWidget};
+pub use view::{AmplitudeUnit, WaveformView};
+pub use widget::WaveformDisplayWidget;
+
+pub(crate) use samples::ChannelSamples;
+pub(crate) use view::{grid_lines, nice_step};
+
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum SignalEdge {
+    FallingEdge,
+    RisingEdge,
+}
+
+// ----------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BufferLayout {
+    Planar,
+    Interleaved,
+}
+
+// ----------------------------------------------------------------------------
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Display, EnumIter, Eq, PartialEq)]
+pub enum ChannelLayout {
+    #[strum(to_string = "Side by side")]
+    SideBySide,
+
+    #[strum(to_string = "Stacked")]
+    Stacked,
+
+    /// All channels in one pane, toggled from the legend in the header.
+    #[strum(to_string = "Overlay")]
+    Overlay,
+}
+
+// ----------------------------------------------------------------------------
+
+pub trait SampleRange<T> {
+    const ZERO: T;
+    const DISPLAY_RANGE: RangeInclusive<f32>;
+}
+
+impl SampleRange<u8> for u8 {
+    const ZERO: u8 = 128;
+    const DISPLAY_RANGE: RangeInclusive<f32> = 0.0..=255.0;
+}
+
+impl SampleRange<i8> for i8 {
+    const ZERO: i8 = 0;
+    const DISPLAY_RANGE: RangeInclusive<f32> = -128.0..=127.0;
+}
+
+impl SampleRange<u16> for u16 {
+    const ZERO: u16 = 32768;
+    const DISPLAY_RANGE: RangeInclusive<f32> = 0.0..=65535.0;
+}
+
+impl SampleRange<i16> for i16 {
+    const ZERO: i16 = 0;
+    const DISPLAY_RANGE: RangeInclusive<f32> = -32768.0..=32767.0;
+}
+
+impl SampleRange<u32> for u32 {
+    const ZERO: u32 = 2147483648;
+    const DISPLAY_RANGE: RangeInclusive<f32> = 0.0..=4294967295.0;
+}
+
+impl SampleRange<i32> for i32 {
+    const ZERO: i32 = 0;
+    const DISPLAY_RANGE: RangeInclusive<f32> = -2147483648.0..=2147483647.0;
+}
+
+impl SampleRange<f32> for f32 {
+    const ZERO: f32 = 0.0;
+    const DISPLAY_RANGE: RangeInclusive<f32> = -1.0..=1.0;
+}
+
+impl SampleRange<f64> for f64 {
+    const ZERO: f64 = 0.0;
+    const DISPLAY_RANGE: RangeInclusive<f32> = -1.0..=1.0;
+}
@@ -0,0 +1,317 @@
+use std::ops::Range;
+
+use itertools::Itertools;
+
+use crate::displays::waveform_display::BufferLayout;
+
+// ----------------------------------------------------------------------------
+
+fn merge_min_max(a: (f32, f32), b: (f32, f32)) -> (f32, f32) {
+    (a.0.min(b.0), a.1.max(b.1))
+}
+
+fn min_max(samples: impl Iterator<Item = f32>) -> Option<(f32, f32)> {
+    samples.map(|sample| (sample, sample)).reduce(merge_min_max)
+}
+
+// ----------------------------------------------------------------------------
+
+/// Samples of one channel, read in place from a planar or interleaved buffer.
+#[derive(Clone, Copy)]
+pub(crate) struct ChannelSamples<'a, T> {
+    buffer: &'a [T],
+    offset: usize,
+    stride: usize,
+    len: usize,
+}
+
+impl<'a, T: Copy + Into<f32>> ChannelSamples<'a, T> {
+    pub(crate) fn new(
+        buffer: &'a [T],
+        buffer_layout: BufferLayout,
+        channels: usize,
+        channel_id: usize,
+    ) -> Self {
+        let len = buffer.len() / channels;
+
+        let (offset, stride) = match buffer_layout {
+            _ if channels == 1 => (0, 1),
+            BufferLayout::Planar => (channel_id * len, 1),
+            BufferLayout::Interleaved => (channel_id, channels),
+        };
+
+        Self {
+            buffer,
+            offset,
+            stride,
+            len,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    pub(crate) fn get(&self, index: usize) -> f32 {
+        self.buffer[self.offset + index * self.stride].into()
+    }
+
+    pub(crate) fn iter(&self, range: Range<usize>) -> impl Iterator<Item = f32> + 'a {
+        let (buffer, offset, stride) = (self.buffer, self.offset, self.stride);
+        range.map(move |index| buffer[offset + index * stride].into())
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Min/max envelope of a buffer precomputed at several resolutions, so that
+/// long buffers can be drawn without visiting every sample each frame. Build
+/// it once per buffer and pass it to
+/// [`WaveformDisplayWidget::peak_cache`](crate::displays::WaveformDisplayWidget::peak_cache).
+#[derive(Clone, Debug)]
+pub struct PeakCache {
+    /// Per channel, per level, min/max of each block.
+    levels: Vec<Vec<Vec<(f32, f32)>>>,
+    channel_length: usize,
+}
+
+impl PeakCache {
+    /// Block size of the finest level, in samples.
+    const BASE_BLOCK_SIZE: usize = 16;
+
+    /// Number of blocks merged into one on the next level.
+    const LEVEL_FACTOR: usize = 4;
+
+    pub fn new<T: Copy + Into<f32>>(
+        buffer: &[T],
+        buffer_layout: BufferLayout,
+        channels: usize,
+    ) -> Self {
+        if channels == 0 {
+            return Self {
+                levels: Vec::new(),
+                channel_length: 0,
+            };
+        }
+
+        assert_eq!(buffer.len() % channels, 0);
+
+        let channel_length = buffer.len() / channels;
+
+        let levels = (0..channels)
+            .map(|channel_id| {
+                let samples = ChannelSamples::new(buffer, buffer_layout, channels, channel_id);
+
+                let mut levels = vec![(0..channel_length)
+                    .step_by(Self::BASE_BLOCK_SIZE)
+                    .filter_map(|start| {
+                        min_max(
+                            samples
+                                .iter(start..(start + Self::BASE_BLOCK_SIZE).min(channel_length)),
+                        )
+                    })
+                    .collect_vec()];
+
+                while let Some(blocks) = levels.last().filter(|blocks| blocks.len() > 1) {
+                    let next_level = blocks
+                        .chunks(Self::LEVEL_FACTOR)
+                        .filter_map(|chunk| chunk.iter().copied().reduce(merge_min_max))
+                        .collect_vec();
+
+                    levels.push(next_level);
+                }
+
+                levels
+            })
+            .collect_vec();
+
+        Self {
+            levels,
+            channel_length,
+        }
+    }
+
+    #[must_use]
+    pub fn channels(&self) -> usize {
+        self.levels.len()
+    }
+
+    #[must_use]
+    pub fn channel_length(&self) -> usize {
+        self.channel_length
+    }
+
+    pub(crate) fn min_max<T: Copy + Into<f32>>(
+        &self,
+        channel_id: usize,
+        samples: &ChannelSamples<'_, T>,
+        range: Range<usize>,
+    ) -> Option<(f32, f32)> {
+        Self::level_min_max(&self.levels[channel_id], samples, range)
+    }
+
+    /// Uses the whole blocks of the coarsest level in `levels` that fit in
+    /// `range`, and finer levels for the remainder at both ends.
+    fn level_min_max<T: Copy + Into<f32>>(
+        levels: &[Vec<(f32, f32)>],
+        samples: &ChannelSamples<'_, T>,
+        range: Range<usize>,
+    ) -> Option<(f32, f32)> {
+        let Some((blocks, finer_levels)) = levels.split_last() else {
+            return min_max(samples.iter(range));
+        };
+
+        let block_size = Self::BASE_BLOCK_SIZE * Self::LEVEL_FACTOR.pow(finer_levels.len() as u32);
+
+        let first_block = range.start.div_ceil(block_size);
+        let last_block = range.end / block_size;
+
+        if first_block >= last_block {
+            return Self::level_min_max(finer_levels, samples, range);
+        }
+
+        [
+            Self::level_min_max(
+                finer_levels,
+                samples,
+                range.start..(first_block * block_size),
+            ),
+            blocks[first_block..last_block]
+                .iter()
+                .copied()
+                .reduce(merge_min_max),
+            Self::level_min_max(finer_levels, samples, (last_block * block_size)..range.end),
+        ]
+        .into_iter()
+        .flatten()
+        .reduce(merge_min_max)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Samples of one channel inside the displayed window.
+pub(crate) enum WindowSamples<'a, T> {
+    /// Read in place from the buffer, starting at `start`.
+    Live {
+        samples: ChannelSamples<'a, T>,
+        start: usize,
+        len: usize,
+        peak_cache: Option<(&'a PeakCache, usize)>,
+    },
+
+    /// Copied at the last trigger event.
+    Captured(&'a [f32]),
+}
+
+impl<T: Copy + Into<f32>> WindowSamples<'_, T> {
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            WindowSamples::Live { len, .. } => *len,
+            WindowSamples::Captured(samples) => samples.len(),
+        }
+    }
+
+    pub(crate) fn get(&self, index: usize) -> f32 {
+        match self {
+            WindowSamples::Live { samples, start, .. } => samples.get(start + index),
+            WindowSamples::Captured(samples) => samples[index],
+        }
+    }
+
+    /// Like [`Self::get`], `None` past the end of a capture shorter than the
+    /// window.
+    pub(crate) fn get_checked(&self, index: usize) -> Option<f32> {
+        (index < self.len()).then(|| self.get(index))
+    }
+
+    pub(crate) fn min_max(&self, range: Range<usize>) -> Option<(f32, f32)> {
+        match self {
+            WindowSamples::Live {
+                samples,
+                start,
+                peak_cache: Some((peak_cache, channel_id)),
+                ..
+            } => peak_cache.min_max(
+                *channel_id,
+                samples,
+                (start + range.start)..(start + range.end),
+            ),
+            WindowSamples::Live { samples, start, .. } => {
+                min_max(samples.iter((start + range.start)..(start + range.end)))
+            }
+            WindowSamples::Captured(samples) => min_max(samples[range].iter().copied()),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random numbers, a 64-bit LCG.
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self) -> u64 {
+            self.0 = self
+                .0
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            self.0 >> 33
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+    }
+
+    #[test]
+    fn peak_cache_matches_scan() {
+        let mut rng = Lcg(42);
+
+        for buffer_layout in [BufferLayout::Planar, BufferLayout::Interleaved] {
+            for (channels, channel_length) in [(1, 1), (1, 15), (2, 1000), (3, 5000)] {
+                let buffer = (0..channels * channel_length)
+                    .map(|_| rng.below(2001) as f32 / 1000.0 - 1.0)
+                    .collect_vec();
+
+                let peak_cache = PeakCache::new(&buffer, buffer_layout, channels);
+                assert_eq!(peak_cache.channels(), channels);
+                assert_eq!(peak_cache.channel_length(), channel_length);
+
+                for channel_id in 0..channels {
+                    let samples = ChannelSamples::new(&buffer, buffer_layout, channels, channel_id);
+
+                    for _ in 0..200 {
+                        let start = rng.below(channel_length + 1);
+                        let end = start + rng.below(channel_length - start + 1);
+
+                        assert_eq!(
+                            peak_cache.min_max(channel_id, &samples, start..end),
+                            min_max(samples.iter(start..end)),
+                            "{buffer_layout:?} {channels}x{channel_length} {start}..{end}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn peak_cache_without_channels() {
+        let peak_cache = PeakCache::new::<f32>(&[], BufferLayout::Planar, 0);
+        assert_eq!(peak_cache.channels(), 0);
+        assert_eq!(peak_cache.channel_length(), 0);
+    }
+
+    #[test]
+    fn short_capture() {
+        let window = WindowSamples::<f32>::Captured(&[0.25, 0.5]);
+        assert_eq!(window.get_checked(1), Some(0.5));
+        assert_eq!(window.get_checked(2), None);
+        assert_eq!(window.get_checked(1000), None);
+    }
+}
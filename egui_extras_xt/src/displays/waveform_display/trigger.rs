@@ -0,0 +1,333 @@
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+use egui::{Context, Id};
+use strum::{Display, EnumIter};
+
+use crate::displays::waveform_display::SignalEdge;
+
+// ----------------------------------------------------------------------------
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Display, EnumIter, Eq, PartialEq)]
+pub enum TriggerSlope {
+    #[strum(to_string = "Rising")]
+    Rising,
+
+    #[strum(to_string = "Falling")]
+    Falling,
+
+    #[strum(to_string = "Either")]
+    Either,
+}
+
+impl TriggerSlope {
+    fn matches(&self, signal_edge: SignalEdge) -> bool {
+        match *self {
+            TriggerSlope::Rising => signal_edge == SignalEdge::RisingEdge,
+            TriggerSlope::Falling => signal_edge == SignalEdge::FallingEdge,
+            TriggerSlope::Either => true,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Display, EnumIter, Eq, PartialEq)]
+pub enum TriggerMode {
+    /// Free-runs when there is no trigger event.
+    #[strum(to_string = "Auto")]
+    Auto,
+
+    /// Keeps showing the last triggered capture when there is no trigger
+    /// event.
+    #[strum(to_string = "Normal")]
+    Normal,
+
+    /// Captures the first trigger event, then stops until re-armed with
+    /// [`Trigger::rearm`].
+    #[strum(to_string = "Single")]
+    Single,
+}
+
+#[derive(Clone, Copy, Debug, Default, Display, Eq, PartialEq)]
+pub enum TriggerStatus {
+    /// Waiting for a trigger event.
+    #[default]
+    #[strum(to_string = "Armed")]
+    Armed,
+
+    #[strum(to_string = "Triggered")]
+    Triggered,
+
+    /// Free-running in [`TriggerMode::Auto`].
+    #[strum(to_string = "Auto")]
+    Auto,
+
+    /// Holding the capture of [`TriggerMode::Single`].
+    #[strum(to_string = "Stopped")]
+    Stopped,
+}
+
+impl TriggerStatus {
+    /// Status of the waveform display with the given response id.
+    #[must_use]
+    pub fn load(ctx: &Context, id: Id) -> Self {
+        ctx.data(|data| data.get_temp::<TriggerState>(id))
+            .map(|state| state.status)
+            .unwrap_or_default()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Oscilloscope-style trigger. Levels are in the units of
+/// [`SampleRange::DISPLAY_RANGE`](crate::displays::waveform_display::SampleRange).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Trigger {
+    pub level: f32,
+    pub slope: TriggerSlope,
+
+    /// Distance the signal has to move away from the level on the opposite
+    /// side before the next crossing counts, rejecting noise.
+    pub hysteresis: f32,
+
+    /// Minimum time between two trigger events, in seconds. The previous
+    /// capture is shown meanwhile.
+    pub holdoff: f32,
+
+    pub mode: TriggerMode,
+
+    /// Channel whose crossings trigger all channels. Each channel triggers
+    /// on its own signal when `None`.
+    pub source_channel: Option<usize>,
+
+    /// Horizontal position of the trigger point in the window, from `0.0`
+    /// (left edge) to `1.0` (right edge).
+    pub position: f32,
+}
+
+impl Default for Trigger {
+    fn default() -> Self {
+        Self {
+            level: 0.0,
+            slope: TriggerSlope::Rising,
+            hysteresis: 0.0,
+            holdoff: 0.0,
+            mode: TriggerMode::Auto,
+            source_channel: None,
+            position: 0.5,
+        }
+    }
+}
+
+impl Trigger {
+    pub fn new(level: f32) -> Self {
+        Self {
+            level,
+            ..Default::default()
+        }
+    }
+
+    pub fn slope(mut self, slope: TriggerSlope) -> Self {
+        self.slope = slope;
+        self
+    }
+
+    pub fn hysteresis(mut self, hysteresis: f32) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    pub fn holdoff(mut self, holdoff: f32) -> Self {
+        self.holdoff = holdoff;
+        self
+    }
+
+    pub fn mode(mut self, mode: TriggerMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn source_channel(mut self, source_channel: Option<usize>) -> Self {
+        self.source_channel = source_channel;
+        self
+    }
+
+    pub fn position(mut self, position: f32) -> Self {
+        self.position = position.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Re-arms [`TriggerMode::Single`] on the waveform display with the given
+    /// response id, discarding its capture.
+    pub fn rearm(ctx: &Context, id: Id) {
+        ctx.data_mut(|data| data.remove::<TriggerState>(id));
+    }
+
+    /// Number of samples left of the trigger point.
+    pub(crate) fn pretrigger_length(&self, window_size: usize) -> usize {
+        ((window_size as f32 * self.position).round() as usize).min(window_size)
+    }
+
+    /// Index of the trigger event in `valid_range` closest to `target`.
+    pub(crate) fn find(
+        &self,
+        samples: impl IntoIterator<Item = f32>,
+        valid_range: RangeInclusive<usize>,
+        target: usize,
+    ) -> Option<usize> {
+        let mut samples = samples.into_iter();
+        let mut previous = samples.next()?;
+
+        let mut rising_armed = previous < self.level - self.hysteresis;
+        let mut falling_armed = previous >= self.level + self.hysteresis;
+
+        let mut best: Option<usize> = None;
+
+        for (index, sample) in (1..).zip(samples) {
+            if index > *valid_range.end() {
+                break;
+            }
+
+            let signal_edge = if rising_armed && (previous < self.level) && (sample >= self.level) {
+                rising_armed = false;
+                Some(SignalEdge::RisingEdge)
+            } else if falling_armed && (previous >= self.level) && (sample < self.level) {
+                falling_armed = false;
+                Some(SignalEdge::FallingEdge)
+            } else {
+                None
+            };
+
+            rising_armed |= sample < self.level - self.hysteresis;
+            falling_armed |= sample >= self.level + self.hysteresis;
+            previous = sample;
+
+            if signal_edge.is_some_and(|signal_edge| self.slope.matches(signal_edge))
+                && valid_range.contains(&index)
+            {
+                if best.map_or(true, |best| index.abs_diff(target) < best.abs_diff(target)) {
+                    best = Some(index);
+                }
+
+                // Later events are only farther away
+                if index >= target {
+                    break;
+                }
+            }
+        }
+
+        best
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Per-widget trigger bookkeeping, stored in egui memory.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TriggerState {
+    pub status: TriggerStatus,
+    pub last_trigger_time: Option<f64>,
+
+    /// Window samples of each channel at the last trigger event.
+    pub capture: Option<Arc<Vec<Vec<f32>>>>,
+}
+
+impl TriggerState {
+    /// Re-arms when the capture no longer matches the channel count or window
+    /// size.
+    pub fn discard_stale_capture(&mut self, channels: usize, window_size: usize) {
+        let stale = self.capture.as_ref().is_some_and(|capture| {
+            (capture.len() != channels) || capture.iter().any(|window| window.len() != window_size)
+        });
+
+        if stale {
+            *self = Self::default();
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: [f32; 8] = [-1.0, 1.0, -1.0, 1.0, -1.0, 1.0, -1.0, 1.0];
+
+    #[test]
+    fn find_slope() {
+        let trigger = Trigger::new(0.0);
+        assert_eq!(trigger.find([-1.0, -0.5, 0.5, 1.0], 0..=3, 0), Some(2));
+        assert_eq!(trigger.find([1.0, 0.5, -0.5, -1.0], 0..=3, 0), None);
+
+        let trigger = trigger.slope(TriggerSlope::Falling);
+        assert_eq!(trigger.find([1.0, 0.5, -0.5, -1.0], 0..=3, 0), Some(2));
+        assert_eq!(trigger.find([-1.0, -0.5, 0.5, 1.0], 0..=3, 0), None);
+
+        let trigger = trigger.slope(TriggerSlope::Either);
+        assert_eq!(trigger.find(SQUARE, 0..=7, 2), Some(2));
+    }
+
+    #[test]
+    fn find_closest_to_target() {
+        let trigger = Trigger::new(0.0);
+
+        // Rising edges at 1, 3, 5 and 7
+        assert_eq!(trigger.find(SQUARE, 0..=7, 0), Some(1));
+        assert_eq!(trigger.find(SQUARE, 0..=7, 5), Some(5));
+        assert_eq!(trigger.find(SQUARE, 0..=7, 6), Some(5));
+        assert_eq!(trigger.find(SQUARE, 0..=7, 100), Some(7));
+        assert_eq!(trigger.find(SQUARE, 2..=4, 0), Some(3));
+        assert_eq!(trigger.find(SQUARE, 4..=4, 0), None);
+    }
+
+    #[test]
+    fn find_hysteresis() {
+        let noisy = [-1.0, 0.1, -0.05, 0.1, -0.05, 0.1, -1.0, 0.1];
+
+        let trigger = Trigger::new(0.0);
+        assert_eq!(trigger.find(noisy, 0..=7, 5), Some(5));
+
+        let trigger = trigger.hysteresis(0.2);
+        assert_eq!(trigger.find(noisy, 0..=7, 5), Some(7));
+        assert_eq!(trigger.find(noisy, 0..=6, 5), Some(1));
+    }
+
+    #[test]
+    fn find_without_samples() {
+        let trigger = Trigger::new(0.0);
+        assert_eq!(trigger.find([], 0..=0, 0), None);
+        assert_eq!(trigger.find([1.0], 0..=0, 0), None);
+    }
+
+    #[test]
+    fn pretrigger_length() {
+        assert_eq!(Trigger::new(0.0).pretrigger_length(100), 50);
+        assert_eq!(Trigger::new(0.0).position(0.0).pretrigger_length(100), 0);
+        assert_eq!(Trigger::new(0.0).position(2.0).pretrigger_length(100), 100);
+    }
+
+    #[test]
+    fn discard_stale_capture() {
+        let capture = Arc::new(vec![vec![0.0; 4]; 2]);
+        let mut state = TriggerState {
+            status: TriggerStatus::Stopped,
+            last_trigger_time: Some(1.0),
+            capture: Some(capture),
+        };
+
+        state.discard_stale_capture(2, 4);
+        assert_eq!(state.status, TriggerStatus::Stopped);
+        assert!(state.capture.is_some());
+
+        state.discard_stale_capture(2, 8);
+        assert_eq!(state.status, TriggerStatus::Armed);
+        assert_eq!(state.last_trigger_time, None);
+        assert!(state.capture.is_none());
+
+        state.capture = Some(Arc::new(vec![vec![0.0; 4]; 2]));
+        state.discard_stale_capture(1, 4);
+        assert!(state.capture.is_none());
+    }
+}
@@ -0,0 +1,427 @@
+use std::collections::VecDeque;
+use std::f32::consts::FRAC_1_SQRT_2;
+use std::sync::Arc;
+
+use egui::{
+    pos2, remap, vec2, Align2, Color32, FontId, FontSelection, Mesh, Rect, Response, Sense, Stroke,
+    Ui, Vec2, Widget,
+};
+use itertools::Itertools;
+use strum::{Display, EnumIter};
+
+use crate::displays::waveform_display::samples::ChannelSamples;
+use crate::displays::waveform_display::{BufferLayout, SampleRange};
+use crate::displays::BarGraphZone;
+
+// ----------------------------------------------------------------------------
+
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Display, EnumIter, Eq, PartialEq)]
+pub enum VectorscopeMode {
+    /// First channel on the horizontal axis, second channel on the vertical
+    /// axis (Lissajous figure).
+    #[strum(to_string = "XY")]
+    Xy,
+
+    /// Rotated by 45 degrees, with mid (mono) on the vertical axis and side
+    /// on the horizontal axis (goniometer).
+    #[strum(to_string = "M/S")]
+    MidSide,
+}
+
+impl VectorscopeMode {
+    fn project(&self, x: f32, y: f32) -> Vec2 {
+        match *self {
+            VectorscopeMode::Xy => vec2(x, y),
+            VectorscopeMode::MidSide => vec2(y - x, x + y) * FRAC_1_SQRT_2,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Points of earlier frames, faded out over the persistence time.
+#[derive(Clone, Default)]
+struct PhosphorHistory {
+    frames: VecDeque<(f64, Arc<Vec<Vec2>>)>,
+}
+
+/// Pearson correlation of two channels, from `-1.0` (out of phase) to `1.0`
+/// (mono).
+fn correlation(samples: impl Iterator<Item = (f32, f32)>) -> f32 {
+    let (xy, xx, yy) = samples.fold((0.0, 0.0, 0.0), |(xy, xx, yy), (x, y)| {
+        let (x, y) = (x as f64, y as f64);
+        (xy + x * y, xx + x * x, yy + y * y)
+    });
+
+    if (xx > 0.0) && (yy > 0.0) {
+        (xy / (xx * yy).sqrt()) as f32
+    } else {
+        0.0
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Plots two channels against each other, with a correlation meter below.
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct VectorscopeWidget<'a, SampleType>
+where
+    SampleType: SampleRange<SampleType>,
+{
+    buffer: &'a [SampleType],
+    buffer_layout: BufferLayout,
+    channels: usize,
+    channel_pair: (usize, usize),
+    window_size: usize,
+    width: f32,
+    height: f32,
+    mode: VectorscopeMode,
+    gain: f32,
+    color: Option<Color32>,
+    dot_size: f32,
+    persistence: f32,
+    show_graticule: bool,
+    show_correlation: bool,
+}
+
+impl<'a, SampleType> VectorscopeWidget<'a, SampleType>
+where
+    SampleType: SampleRange<SampleType>,
+{
+    pub fn new(buffer: &'a [SampleType]) -> Self {
+        Self {
+            buffer,
+            buffer_layout: BufferLayout::Interleaved,
+            channels: 2,
+            channel_pair: (0, 1),
+            window_size: 2048,
+            width: 160.0,
+            height: 184.0,
+            mode: VectorscopeMode::MidSide,
+            gain: 1.0,
+            color: None,
+            dot_size: 1.5,
+            persistence: 0.25,
+            show_graticule: true,
+            show_correlation: true,
+        }
+    }
+
+    pub fn buffer_layout(mut self, buffer_layout: BufferLayout) -> Self {
+        self.buffer_layout = buffer_layout;
+        self
+    }
+
+    pub fn channels(mut self, channels: usize) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    /// Channels plotted on the horizontal (or left) and vertical (or right)
+    /// axes.
+    pub fn channel_pair(mut self, x_channel: usize, y_channel: usize) -> Self {
+        self.channel_pair = (x_channel, y_channel);
+        self
+    }
+
+    /// Number of most recent samples plotted, 2048 by default. Every sample
+    /// of every persisted frame is drawn as a dot.
+    pub fn window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<f32>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<f32>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    pub fn mode(mut self, mode: VectorscopeMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn gain(mut self, gain: f32) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    pub fn color(mut self, color: Color32) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn dot_size(mut self, dot_size: f32) -> Self {
+        self.dot_size = dot_size;
+        self
+    }
+
+    /// Time in seconds for the points of earlier frames to fade to about a
+    /// third of their brightness. Zero shows the current frame only.
+    pub fn persistence(mut self, persistence: f32) -> Self {
+        self.persistence = persistence;
+        self
+    }
+
+    pub fn show_graticule(mut self, show_graticule: bool) -> Self {
+        self.show_graticule = show_graticule;
+        self
+    }
+
+    pub fn show_correlation(mut self, show_correlation: bool) -> Self {
+        self.show_correlation = show_correlation;
+        self
+    }
+}
+
+impl<'a, SampleType> Widget for VectorscopeWidget<'a, SampleType>
+where
+    SampleType: SampleRange<SampleType> + Into<f32> + Copy,
+{
+    fn ui(self, ui: &mut Ui) -> Response {
+        let desired_size = vec2(self.width, self.height);
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        assert_eq!(self.buffer.len() % self.channels, 0);
+        assert!(self.channel_pair.0 < self.channels);
+        assert!(self.channel_pair.1 < self.channels);
+
+        let channel_buffer_length = self.buffer.len() / self.channels;
+        let window_size = self.window_size.min(channel_buffer_length);
+        let window = (channel_buffer_length - window_size)..channel_buffer_length;
+
+        let normalize = |sample: f32| remap(sample, SampleType::DISPLAY_RANGE, -1.0..=1.0);
+
+        let [x_samples, y_samples] = [self.channel_pair.0, self.channel_pair.1].map(|channel_id| {
+            ChannelSamples::new(self.buffer, self.buffer_layout, self.channels, channel_id)
+        });
+
+        let sample_pairs = || {
+            x_samples
+                .iter(window.clone())
+                .zip(y_samples.iter(window.clone()))
+                .map(|(x, y)| (normalize(x), normalize(y)))
+        };
+
+        // Phosphor history
+        let time = ui.input(|input| input.time);
+
+        let points = Arc::new(
+            sample_pairs()
+                .map(|(x, y)| self.mode.project(x, y) * self.gain)
+                .collect_vec(),
+        );
+
+        let mut history = ui
+            .memory(|memory| memory.data.get_temp::<PhosphorHistory>(response.id))
+            .unwrap_or_default();
+
+        if history
+            .frames
+            .back()
+            .map_or(true, |(_, last_points)| *last_points != points)
+        {
+            history.frames.push_back((time, points));
+        }
+
+        let brightness = |frame_time: f64| {
+            if self.persistence > 0.0 {
+                (-(time - frame_time) as f32 / self.persistence).exp()
+            } else {
+                0.0
+            }
+        };
+
+        while history.frames.len() > 1 && brightness(history.frames[0].0) < 0.02 {
+            history.frames.pop_front();
+        }
+
+        if history.frames.len() > 1 {
+            ui.ctx().request_repaint();
+        }
+
+        ui.memory_mut(|memory| memory.data.insert_temp(response.id, history.clone()));
+
+        if ui.is_rect_visible(rect) {
+            let visuals = *ui.style().noninteractive();
+
+            let font_id = FontSelection::Default.resolve(ui.style());
+            let label_font_id = FontId::new(font_id.size * 0.75, font_id.family.clone());
+            let label_color = ui.style().visuals.weak_text_color();
+            let grid_color = visuals.bg_stroke.color;
+            let color = self.color.unwrap_or(visuals.fg_stroke.color);
+
+            ui.painter().rect(
+                rect,
+                visuals.rounding,
+                ui.style().visuals.extreme_bg_color,
+                visuals.fg_stroke,
+            );
+
+            let meter_height = if self.show_correlation {
+                font_id.size * 1.5
+            } else {
+                0.0
+            };
+
+            let scope_rect = {
+                let mut tmp = rect.shrink(4.0);
+                tmp.set_height(tmp.height() - meter_height);
+                let side = tmp.width().min(tmp.height());
+                Rect::from_center_size(tmp.center(), vec2(side, side))
+            };
+
+            let to_screen = |point: Vec2| {
+                pos2(
+                    remap(point.x, -1.0..=1.0, scope_rect.x_range()),
+                    remap(point.y, -1.0..=1.0, scope_rect.bottom_up_range()),
+                )
+            };
+
+            let painter = ui.painter_at(scope_rect);
+
+            // Graticule
+            if self.show_graticule {
+                let axes: &[(Vec2, &str, &str)] = match self.mode {
+                    VectorscopeMode::Xy => &[(vec2(1.0, 0.0), "X", ""), (vec2(0.0, 1.0), "Y", "")],
+                    VectorscopeMode::MidSide => &[
+                        (vec2(0.0, 1.0), "M", ""),
+                        (vec2(1.0, 0.0), "+S", "-S"),
+                        (vec2(-1.0, 1.0) * FRAC_1_SQRT_2, "L", ""),
+                        (vec2(1.0, 1.0) * FRAC_1_SQRT_2, "R", ""),
+                    ],
+                };
+
+                for &(axis, positive_label, negative_label) in axes {
+                    painter.line_segment([to_screen(-axis), to_screen(axis)], (1.0, grid_color));
+
+                    for (end, label) in [(axis, positive_label), (-axis, negative_label)] {
+                        painter.text(
+                            to_screen(end * 0.9),
+                            Align2::CENTER_CENTER,
+                            label,
+                            label_font_id.clone(),
+                            label_color,
+                        );
+                    }
+                }
+
+                painter.circle_stroke(
+                    scope_rect.center(),
+                    scope_rect.width() / 2.0,
+                    (1.0, grid_color),
+                );
+            }
+
+            // Points, oldest first
+            let mut mesh = Mesh::default();
+            let dot_size = vec2(self.dot_size, self.dot_size);
+            let last_frame = history.frames.len() - 1;
+
+            for (frame_index, (frame_time, points)) in history.frames.iter().enumerate() {
+                let frame_color = if frame_index == last_frame {
+                    color
+                } else {
+                    color.gamma_multiply(brightness(*frame_time))
+                };
+
+                for &point in points.iter() {
+                    mesh.add_colored_rect(
+                        Rect::from_center_size(to_screen(point), dot_size),
+                        frame_color,
+                    );
+                }
+            }
+
+            painter.add(mesh);
+
+            // Correlation meter
+            if self.show_correlation {
+                let correlation = correlation(sample_pairs());
+
+                let meter_rect = Rect::from_min_max(
+                    pos2(rect.left() + 4.0, rect.bottom() - meter_height),
+                    rect.right_bottom() - vec2(4.0, 4.0),
+                );
+
+                let label_width = font_id.size * 1.5;
+                let track_rect = meter_rect.shrink2(vec2(label_width, meter_height * 0.2));
+
+                for (text, x, align) in [
+                    ("-1", meter_rect.left(), Align2::LEFT_CENTER),
+                    ("+1", meter_rect.right(), Align2::RIGHT_CENTER),
+                ] {
+                    ui.painter().text(
+                        pos2(x, meter_rect.center().y),
+                        align,
+                        text,
+                        label_font_id.clone(),
+                        label_color,
+                    );
+                }
+
+                ui.painter()
+                    .rect(track_rect, 0.0, Color32::TRANSPARENT, (1.0, grid_color));
+
+                let value_x = remap(correlation, -1.0..=1.0, track_rect.x_range());
+                let center_x = track_rect.center().x;
+
+                ui.painter().rect_filled(
+                    Rect::from_x_y_ranges(
+                        center_x.min(value_x)..=center_x.max(value_x),
+                        track_rect.y_range(),
+                    ),
+                    0.0,
+                    if correlation < 0.0 {
+                        BarGraphZone::RED
+                    } else {
+                        BarGraphZone::GREEN
+                    },
+                );
+
+                ui.painter().vline(
+                    center_x,
+                    track_rect.y_range(),
+                    Stroke::new(1.0, label_color),
+                );
+            }
+        }
+
+        response
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(len: usize) -> impl Iterator<Item = f32> {
+        (0..len).map(|index| (index as f32 * 0.1).sin())
+    }
+
+    #[test]
+    fn correlation_of_mono_and_inverted() {
+        assert!((correlation(sine(1000).map(|x| (x, x))) - 1.0).abs() < 1e-6);
+        assert!((correlation(sine(1000).map(|x| (x, -x))) + 1.0).abs() < 1e-6);
+        assert_eq!(correlation(sine(1000).map(|x| (x, 0.0))), 0.0);
+    }
+
+    #[test]
+    fn project() {
+        assert_eq!(VectorscopeMode::Xy.project(0.5, -0.25), vec2(0.5, -0.25));
+
+        // Mono on the M axis, left only on the L axis, right only on the R axis
+        let mid_side = VectorscopeMode::MidSide;
+        assert!((mid_side.project(0.5, 0.5) - vec2(0.0, FRAC_1_SQRT_2)).length() < 1e-6);
+        assert!((mid_side.project(1.0, 0.0) - vec2(-1.0, 1.0) * FRAC_1_SQRT_2).length() < 1e-6);
+        assert!((mid_side.project(0.0, 1.0) - vec2(1.0, 1.0) * FRAC_1_SQRT_2).length() < 1e-6);
+    }
+}
@@ -0,0 +1,321 @@
+use std::ops::RangeInclusive;
+
+use egui::{Context, Id};
+use strum::Display;
+
+// ----------------------------------------------------------------------------
+
+/// Unit of the vertical axis labels and the cursor readout.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Display, PartialEq)]
+pub enum AmplitudeUnit {
+    /// Sample values as they are stored in the buffer.
+    #[strum(to_string = "Raw")]
+    Raw,
+
+    /// Volts, with full scale mapped to the given voltage.
+    #[strum(to_string = "V")]
+    Volts(f32),
+
+    /// Decibels relative to full scale.
+    #[strum(to_string = "dBFS")]
+    Dbfs,
+}
+
+impl AmplitudeUnit {
+    /// Formats a value normalized to `-1.0..=1.0` (full scale).
+    pub(crate) fn format(
+        &self,
+        normalized: f32,
+        display_range: &RangeInclusive<f32>,
+        precision: usize,
+    ) -> String {
+        match *self {
+            AmplitudeUnit::Raw => {
+                let raw = egui::remap(normalized, -1.0..=1.0, display_range.clone());
+                format!("{raw:.precision$}")
+            }
+            AmplitudeUnit::Volts(full_scale) => {
+                format!("{:.precision$} V", normalized * full_scale)
+            }
+            AmplitudeUnit::Dbfs => {
+                if normalized == 0.0 {
+                    "-\u{221E} dBFS".to_owned()
+                } else {
+                    format!("{:.precision$} dBFS", 20.0 * normalized.abs().log10())
+                }
+            }
+        }
+    }
+
+    /// Grid step and number of decimals for a linear axis spanning `span`
+    /// normalized units, aiming for at most `max_divisions` divisions.
+    pub(crate) fn grid_step(
+        &self,
+        span: f32,
+        display_range: &RangeInclusive<f32>,
+        max_divisions: f32,
+    ) -> (f32, usize) {
+        let scale = match *self {
+            AmplitudeUnit::Raw => (display_range.end() - display_range.start()) / 2.0,
+            AmplitudeUnit::Volts(full_scale) => full_scale.abs(),
+            AmplitudeUnit::Dbfs => 1.0,
+        };
+
+        let step = nice_step(span * scale, max_divisions);
+        (step / scale, decimals(step))
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Zoom and pan state of a waveform display, changed with the mouse wheel
+/// (time), Shift + mouse wheel (amplitude) and dragging. Double-click resets
+/// the view.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WaveformView {
+    time_zoom: f32,
+    time_center: f32,
+    amplitude_zoom: f32,
+    amplitude_center: f32,
+}
+
+impl Default for WaveformView {
+    fn default() -> Self {
+        Self {
+            time_zoom: 1.0,
+            time_center: 0.5,
+            amplitude_zoom: 1.0,
+            amplitude_center: 0.0,
+        }
+    }
+}
+
+impl WaveformView {
+    const MAX_AMPLITUDE_ZOOM: f32 = 1024.0;
+
+    /// View of the waveform display with the given response id.
+    #[must_use]
+    pub fn load(ctx: &Context, id: Id) -> Self {
+        ctx.data(|data| data.get_temp::<Self>(id.with("view")))
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn store(self, ctx: &Context, id: Id) {
+        ctx.data_mut(|data| data.insert_temp(id.with("view"), self));
+    }
+
+    /// Resets zoom and pan of the waveform display with the given response id.
+    pub fn reset(ctx: &Context, id: Id) {
+        ctx.data_mut(|data| data.remove::<Self>(id.with("view")));
+    }
+
+    #[must_use]
+    pub fn time_zoom(&self) -> f32 {
+        self.time_zoom
+    }
+
+    #[must_use]
+    pub fn amplitude_zoom(&self) -> f32 {
+        self.amplitude_zoom
+    }
+
+    /// Visible part of the window, in samples.
+    pub(crate) fn time_range(&self, window_size: usize) -> RangeInclusive<f32> {
+        let last_sample = window_size.saturating_sub(1) as f32;
+        let half_span = 0.5 / self.time_zoom;
+
+        ((self.time_center - half_span) * last_sample)
+            ..=((self.time_center + half_span) * last_sample)
+    }
+
+    /// Visible amplitude range, normalized to full scale.
+    pub(crate) fn amplitude_range(&self) -> RangeInclusive<f32> {
+        let half_span = 1.0 / self.amplitude_zoom;
+        (self.amplitude_center - half_span)..=(self.amplitude_center + half_span)
+    }
+
+    /// Zooms the time axis by `factor`, keeping the point at `anchor` (a
+    /// fraction of the visible width) in place.
+    pub(crate) fn zoom_time(&mut self, factor: f32, anchor: f32, window_size: usize) {
+        let max_zoom = (window_size as f32 / 4.0).max(1.0);
+        let anchor = self.time_center + (anchor - 0.5) / self.time_zoom;
+
+        let time_zoom = (self.time_zoom * factor).clamp(1.0, max_zoom);
+        self.time_center = anchor + (self.time_center - anchor) * (self.time_zoom / time_zoom);
+        self.time_zoom = time_zoom;
+
+        self.clamp();
+    }
+
+    /// Zooms the amplitude axis by `factor`, keeping the point at `anchor` (a
+    /// fraction of the visible height, from the bottom) in place.
+    pub(crate) fn zoom_amplitude(&mut self, factor: f32, anchor: f32) {
+        let anchor = self.amplitude_center + (anchor * 2.0 - 1.0) / self.amplitude_zoom;
+
+        let amplitude_zoom = (self.amplitude_zoom * factor).clamp(1.0, Self::MAX_AMPLITUDE_ZOOM);
+        self.amplitude_center =
+            anchor + (self.amplitude_center - anchor) * (self.amplitude_zoom / amplitude_zoom);
+        self.amplitude_zoom = amplitude_zoom;
+
+        self.clamp();
+    }
+
+    /// Pans by fractions of the visible width and height.
+    pub(crate) fn pan(&mut self, time: f32, amplitude: f32) {
+        self.time_center += time / self.time_zoom;
+        self.amplitude_center += amplitude * 2.0 / self.amplitude_zoom;
+
+        self.clamp();
+    }
+
+    fn clamp(&mut self) {
+        let time_half_span = 0.5 / self.time_zoom;
+        self.time_center = self.time_center.clamp(time_half_span, 1.0 - time_half_span);
+
+        let amplitude_half_span = 1.0 / self.amplitude_zoom;
+        self.amplitude_center = self
+            .amplitude_center
+            .clamp(amplitude_half_span - 1.0, 1.0 - amplitude_half_span);
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Largest 1-2-5 step giving at most `max_divisions` divisions over `span`.
+pub(crate) fn nice_step(span: f32, max_divisions: f32) -> f32 {
+    let raw_step = span / max_divisions.max(1.0);
+    let magnitude = 10.0_f32.powf(raw_step.log10().floor());
+
+    let mantissa = match raw_step / magnitude {
+        x if x <= 1.0 => 1.0,
+        x if x <= 2.0 => 2.0,
+        x if x <= 5.0 => 5.0,
+        _ => 10.0,
+    };
+
+    mantissa * magnitude
+}
+
+/// Number of minor divisions per major `step`.
+pub(crate) fn minor_divisions(step: f32) -> usize {
+    let mantissa = step / 10.0_f32.powf(step.log10().floor());
+
+    if (mantissa - 2.0).abs() < 0.01 {
+        4
+    } else {
+        5
+    }
+}
+
+/// Grid lines at multiples of `step` inside `range`.
+pub(crate) fn grid_lines(range: &RangeInclusive<f32>, step: f32) -> impl Iterator<Item = f32> {
+    let first = (range.start() / step).ceil() as i64;
+    let last = (range.end() / step).floor() as i64;
+
+    (first..=last).map(move |index| index as f32 * step)
+}
+
+/// Number of decimals for labels `step` apart.
+fn decimals(step: f32) -> usize {
+    (-step.log10().floor()).max(0.0) as usize
+}
+
+/// Formats a time in seconds, picking the unit from the grid `step`.
+pub(crate) fn format_time(seconds: f32, step: f32, extra_decimals: usize) -> String {
+    let (scale, unit) = match step.abs() {
+        x if x >= 1.0 => (1.0, "s"),
+        x if x >= 1e-3 => (1e3, "ms"),
+        _ => (1e6, "\u{B5}s"),
+    };
+
+    format!(
+        "{:.*} {unit}",
+        decimals(step * scale) + extra_decimals,
+        seconds * scale
+    )
+}
+
+/// Formats a sample index offset, used when no sample rate is given.
+pub(crate) fn format_samples(samples: f32, step: f32, extra_decimals: usize) -> String {
+    format!("{samples:.*}", decimals(step) + extra_decimals)
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_approx_eq(a: f32, b: f32) {
+        assert!((a - b).abs() <= 1e-5 * b.abs().max(1.0), "{a} != {b}");
+    }
+
+    #[test]
+    fn nice_step() {
+        assert_approx_eq(super::nice_step(10.0, 5.0), 2.0);
+        assert_approx_eq(super::nice_step(1.0, 4.0), 0.5);
+        assert_approx_eq(super::nice_step(7.0, 10.0), 1.0);
+        assert_approx_eq(super::nice_step(100.0, 10.0), 10.0);
+        assert_approx_eq(super::nice_step(0.003, 3.0), 0.001);
+        assert_approx_eq(super::nice_step(3.0, 0.0), 5.0);
+
+        for span in [0.013, 0.7, 1.0, 2.5, 9.9, 42.0, 1234.0] {
+            for max_divisions in [2.0, 5.0, 8.0, 10.0] {
+                let step = super::nice_step(span, max_divisions);
+                let mantissa = step / 10.0_f32.powf(step.log10().floor());
+
+                assert!(
+                    span / step <= max_divisions + 1e-3,
+                    "{span} {max_divisions}"
+                );
+                assert!(
+                    [1.0, 2.0, 5.0, 10.0]
+                        .iter()
+                        .any(|m| (mantissa - m).abs() < 1e-3),
+                    "{span} {max_divisions}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn minor_divisions() {
+        assert_eq!(super::minor_divisions(0.2), 4);
+        assert_eq!(super::minor_divisions(20.0), 4);
+        assert_eq!(super::minor_divisions(0.5), 5);
+        assert_eq!(super::minor_divisions(1.0), 5);
+    }
+
+    #[test]
+    fn grid_lines() {
+        let lines = super::grid_lines(&(-1.0..=1.0), 0.5).collect::<Vec<_>>();
+        assert_eq!(lines, [-1.0, -0.5, 0.0, 0.5, 1.0]);
+
+        let lines = super::grid_lines(&(0.1..=0.9), 0.5).collect::<Vec<_>>();
+        assert_eq!(lines, [0.5]);
+    }
+
+    #[test]
+    fn format_time() {
+        assert_eq!(super::format_time(1.5, 1.0, 1), "1.5 s");
+        assert_eq!(super::format_time(0.002, 0.001, 1), "2.0 ms");
+        assert_eq!(super::format_time(0.00002, 0.00001, 0), "20 \u{B5}s");
+        assert_eq!(super::format_samples(12.0, 0.5, 0), "12.0");
+    }
+
+    #[test]
+    fn zoom_keeps_anchor() {
+        let mut view = WaveformView::default();
+        assert_eq!(view.time_range(1001), 0.0..=1000.0);
+
+        view.zoom_time(2.0, 0.25, 1001);
+        assert_eq!(view.time_range(1001), 125.0..=625.0);
+
+        view.zoom_time(0.25, 0.5, 1001);
+        assert_eq!(view.time_range(1001), 0.0..=1000.0);
+
+        view.zoom_amplitude(4.0, 1.0);
+        assert_eq!(view.amplitude_range(), 0.5..=1.0);
+    }
+}
@@ -0,0 +1,956 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use egui::{
+    pos2, remap, vec2, Align2, Color32, FontId, FontSelection, Pos2, Rect, Response, Sense, Shape,
+    Stroke, Ui, Vec2, Widget,
+};
+use itertools::Itertools;
+
+use crate::displays::waveform_display::samples::{ChannelSamples, WindowSamples};
+use crate::displays::waveform_display::trigger::TriggerState;
+use crate::displays::waveform_display::view::{self, AmplitudeUnit, WaveformView};
+use crate::displays::waveform_display::{
+    BufferLayout, ChannelLayout, PeakCache, SampleRange, Trigger, TriggerMode, TriggerStatus,
+};
+
+// ----------------------------------------------------------------------------
+
+/// Combined into one function (rather than two) to make it easier
+/// for the borrow checker.
+type GetSetValue<'a> = Box<dyn 'a + FnMut(usize, Option<bool>) -> bool>;
+
+fn get(get_set_value: &mut GetSetValue<'_>, channel_id: usize) -> bool {
+    (get_set_value)(channel_id, None)
+}
+
+fn set(get_set_value: &mut GetSetValue<'_>, channel_id: usize, channel_enabled: bool) {
+    (get_set_value)(channel_id, Some(channel_enabled));
+}
+
+// ----------------------------------------------------------------------------
+
+/// Channel colors used by [`ChannelLayout::Overlay`] when none are given.
+const OVERLAY_COLORS: [Color32; 4] = [
+    Color32::from_rgb(0xFF, 0xD7, 0x00),
+    Color32::from_rgb(0x00, 0xD7, 0xFF),
+    Color32::from_rgb(0xFF, 0x40, 0xC0),
+    Color32::from_rgb(0x40, 0xFF, 0x40),
+];
+
+// ----------------------------------------------------------------------------
+
+/// Source of the windows shown in the current frame.
+enum DisplayedWindows {
+    /// Read in place from the buffer.
+    Live,
+
+    /// Copied at an earlier trigger event.
+    Captured(Option<Arc<Vec<Vec<f32>>>>),
+}
+
+// ----------------------------------------------------------------------------
+
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct WaveformDisplayWidget<'a, SampleType>
+where
+    SampleType: SampleRange<SampleType>,
+{
+    get_set_value: GetSetValue<'a>,
+    buffer: Option<&'a [SampleType]>,
+    buffer_layout: BufferLayout,
+    channels: usize,
+    channel_layout: ChannelLayout,
+    channel_colors: Option<Vec<Color32>>,
+    window_size: Option<usize>,
+    width: f32,
+    height: f32,
+    track_name: Option<String>,
+    channel_names: Option<Vec<String>>,
+    show_header: bool,
+    waveform_resolution: usize,
+    trigger: Option<Trigger>,
+    peak_cache: Option<&'a PeakCache>,
+    sample_rate: Option<f32>,
+    amplitude_unit: AmplitudeUnit,
+    show_graticule: bool,
+    show_cursor: bool,
+}
+
+impl<'a, SampleType> WaveformDisplayWidget<'a, SampleType>
+where
+    SampleType: SampleRange<SampleType>,
+{
+    /// All channels are enabled and disabled together.
+    pub fn new(track_enabled: &'a mut bool) -> Self {
+        Self::from_get_set(move |_channel_id: usize, v: Option<bool>| {
+            if let Some(v) = v {
+                *track_enabled = v;
+            }
+            *track_enabled
+        })
+    }
+
+    /// Each channel is enabled and disabled on its own, the number of
+    /// channels is taken from `channels_enabled`.
+    pub fn per_channel(channels_enabled: &'a mut [bool]) -> Self {
+        let channels = channels_enabled.len();
+
+        Self::from_get_set(move |channel_id: usize, v: Option<bool>| {
+            if let Some(v) = v {
+                channels_enabled[channel_id] = v;
+            }
+            channels_enabled[channel_id]
+        })
+        .channels(channels)
+    }
+
+    pub fn from_get_set(get_set_value: impl 'a + FnMut(usize, Option<bool>) -> bool) -> Self {
+        Self {
+            get_set_value: Box::new(get_set_value),
+            buffer: None,
+            buffer_layout: BufferLayout::Interleaved,
+            channels: 1,
+            channel_layout: ChannelLayout::SideBySide,
+            channel_colors: None,
+            window_size: None,
+            width: 256.0,
+            height: 64.0,
+            track_name: None,
+            channel_names: None,
+            show_header: true,
+            waveform_resolution: 1,
+            trigger: None,
+            peak_cache: None,
+            sample_rate: None,
+            amplitude_unit: AmplitudeUnit::Raw,
+            show_graticule: true,
+            show_cursor: true,
+        }
+    }
+
+    pub fn buffer(mut self, buffer: &'a [SampleType]) -> Self {
+        self.buffer = Some(buffer);
+        self
+    }
+
+    pub fn buffer_layout(mut self, buffer_layout: BufferLayout) -> Self {
+        self.buffer_layout = buffer_layout;
+        self
+    }
+
+    pub fn channels(mut self, channels: usize) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    pub fn channel_layout(mut self, channel_layout: ChannelLayout) -> Self {
+        self.channel_layout = channel_layout;
+        self
+    }
+
+    /// Waveform color of each channel. Defaults to the widget foreground
+    /// color, or a distinct color per channel in [`ChannelLayout::Overlay`].
+    pub fn channel_colors(mut self, channel_colors: &[Color32]) -> Self {
+        self.channel_colors = Some(channel_colors.to_vec());
+        self
+    }
+
+    pub fn window_size(mut self, window_size: usize) -> Self {
+        self.window_size = Some(window_size);
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<f32>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<f32>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    pub fn track_name(mut self, track_name: impl ToString) -> Self {
+        self.track_name = Some(track_name.to_string());
+        self
+    }
+
+    pub fn channel_names(mut self, channel_names: &[impl ToString]) -> Self {
+        self.channel_names = Some(channel_names.iter().map(ToString::to_string).collect_vec());
+        self
+    }
+
+    pub fn show_header(mut self, show_header: bool) -> Self {
+        self.show_header = show_header;
+        self
+    }
+
+    /// Width in points of the min/max columns drawn when more than one
+    /// sample falls on a column.
+    pub fn waveform_resolution(mut self, waveform_resolution: usize) -> Self {
+        self.waveform_resolution = waveform_resolution;
+        self
+    }
+
+    /// Trigger settings, shown with markers on the waveform. Without it the
+    /// display locks to the rising zero crossing closest to the middle of the
+    /// buffer.
+    pub fn trigger(mut self, trigger: Trigger) -> Self {
+        self.trigger = Some(trigger);
+        self
+    }
+
+    /// Precomputed envelope of the buffer, for drawing long buffers quickly.
+    /// Ignored when its size doesn't match the buffer.
+    pub fn peak_cache(mut self, peak_cache: &'a PeakCache) -> Self {
+        self.peak_cache = Some(peak_cache);
+        self
+    }
+
+    /// Sample rate in Hz, used to label the time axis. The time axis counts
+    /// samples when not set.
+    pub fn sample_rate(mut self, sample_rate: f32) -> Self {
+        self.sample_rate = Some(sample_rate);
+        self
+    }
+
+    pub fn amplitude_unit(mut self, amplitude_unit: AmplitudeUnit) -> Self {
+        self.amplitude_unit = amplitude_unit;
+        self
+    }
+
+    pub fn show_graticule(mut self, show_graticule: bool) -> Self {
+        self.show_graticule = show_graticule;
+        self
+    }
+
+    /// Shows the time and value of the sample under the mouse pointer.
+    pub fn show_cursor(mut self, show_cursor: bool) -> Self {
+        self.show_cursor = show_cursor;
+        self
+    }
+}
+
+impl<'a, SampleType> Widget for WaveformDisplayWidget<'a, SampleType>
+where
+    SampleType: SampleRange<SampleType> + Into<f32> + Copy + PartialOrd,
+{
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        let desired_size = vec2(self.width, self.height);
+        let (rect, mut response) = ui.allocate_exact_size(desired_size, Sense::click_and_drag());
+
+        let font_id = FontSelection::Default.resolve(ui.style());
+        let label_font_id = FontId::new(font_id.size * 0.75, font_id.family.clone());
+
+        let header_height = font_id.size;
+        let waveform_vertical_margin = 4.0;
+
+        // Each pane shows one channel, or all of them when overlaid
+        let panes = match self.channel_layout {
+            ChannelLayout::Overlay => vec![(rect, (0..self.channels).collect_vec())],
+            ChannelLayout::SideBySide | ChannelLayout::Stacked => (0..self.channels)
+                .map(|channel_id| {
+                    let pane_size = if self.channel_layout == ChannelLayout::Stacked {
+                        rect.size() / vec2(1.0, self.channels as f32)
+                    } else {
+                        rect.size() / vec2(self.channels as f32, 1.0)
+                    };
+
+                    let pane_offset = if self.channel_layout == ChannelLayout::Stacked {
+                        vec2(0.0, pane_size.y * channel_id as f32)
+                    } else {
+                        vec2(pane_size.x * channel_id as f32, 0.0)
+                    };
+
+                    (
+                        Rect::from_min_size(rect.left_top() + pane_offset, pane_size),
+                        vec![channel_id],
+                    )
+                })
+                .collect_vec(),
+        };
+
+        let header_rect = |pane_rect: Rect| {
+            let mut tmp = pane_rect;
+            tmp.set_height(header_height);
+            tmp
+        };
+
+        let waveform_rect = |pane_rect: Rect| {
+            if self.show_header {
+                let mut tmp = pane_rect;
+                tmp = tmp.translate(vec2(0.0, header_height));
+                tmp.set_height(pane_rect.height() - header_height);
+                tmp.shrink2(vec2(0.0, waveform_vertical_margin))
+            } else {
+                pane_rect.shrink2(vec2(0.0, waveform_vertical_margin))
+            }
+        };
+
+        let channel_name = |channel_id: usize| match self.channel_names {
+            Some(ref channel_names) if self.channels > 1 => channel_names.get(channel_id).cloned(),
+            _ => self.track_name.clone(),
+        };
+
+        // Legend of the overlaid channels, centered in the header
+        let legend = if self.show_header && (self.channel_layout == ChannelLayout::Overlay) {
+            let legend_spacing = font_id.size;
+
+            let galleys = (0..self.channels)
+                .map(|channel_id| {
+                    ui.painter().layout_no_wrap(
+                        channel_name(channel_id)
+                            .filter(|_| self.channel_names.is_some())
+                            .unwrap_or_else(|| format!("{}", channel_id + 1)),
+                        font_id.clone(),
+                        Color32::PLACEHOLDER,
+                    )
+                })
+                .collect_vec();
+
+            let legend_width = galleys.iter().map(|galley| galley.size().x).sum::<f32>()
+                + legend_spacing * galleys.len().saturating_sub(1) as f32;
+
+            let mut left = header_rect(rect).center().x - legend_width / 2.0;
+
+            galleys
+                .into_iter()
+                .map(|galley| {
+                    let galley_rect = Rect::from_min_size(
+                        pos2(left, header_rect(rect).center().y - galley.size().y / 2.0),
+                        galley.size(),
+                    );
+                    left += galley.size().x + legend_spacing;
+                    (galley_rect, galley)
+                })
+                .collect_vec()
+        } else {
+            Vec::new()
+        };
+
+        // A click toggles channels once it can no longer become a double-click,
+        // which resets the view instead
+        let pending_click_id = response.id.with("pending_click");
+        let time = ui.input(|input| input.time);
+        let double_click_delay = ui
+            .ctx()
+            .options(|options| options.input_options.max_double_click_delay);
+
+        if response.double_clicked() {
+            ui.data_mut(|data| data.remove::<(Option<Pos2>, f64)>(pending_click_id));
+        } else if response.clicked() {
+            let pointer_pos = response.interact_pointer_pos();
+            ui.data_mut(|data| data.insert_temp(pending_click_id, (pointer_pos, time)));
+        }
+
+        let click = ui
+            .data(|data| data.get_temp::<(Option<Pos2>, f64)>(pending_click_id))
+            .and_then(|(pointer_pos, click_time)| {
+                let remaining = click_time + double_click_delay - time;
+
+                if remaining < 0.0 {
+                    ui.data_mut(|data| data.remove::<(Option<Pos2>, f64)>(pending_click_id));
+                    Some(pointer_pos)
+                } else {
+                    ui.ctx()
+                        .request_repaint_after(Duration::from_secs_f64(remaining));
+                    None
+                }
+            });
+
+        if let Some(pointer_pos) = click {
+            let legend_channel = pointer_pos.and_then(|pointer_pos| {
+                legend
+                    .iter()
+                    .position(|(legend_rect, _)| legend_rect.contains(pointer_pos))
+            });
+
+            let pane_channels = pointer_pos
+                .and_then(|pointer_pos| {
+                    panes
+                        .iter()
+                        .find(|(pane_rect, _)| pane_rect.contains(pointer_pos))
+                })
+                .map_or_else(
+                    || (0..self.channels).collect_vec(),
+                    |(_, channel_ids)| channel_ids.clone(),
+                );
+
+            let toggled_channels =
+                legend_channel.map_or(pane_channels, |channel_id| vec![channel_id]);
+
+            let any_enabled = toggled_channels
+                .iter()
+                .any(|&channel_id| get(&mut self.get_set_value, channel_id));
+
+            for channel_id in toggled_channels {
+                set(&mut self.get_set_value, channel_id, !any_enabled);
+            }
+
+            response.mark_changed();
+        }
+
+        if ui.is_rect_visible(rect) {
+            let channels_enabled = (0..self.channels)
+                .map(|channel_id| get(&mut self.get_set_value, channel_id))
+                .collect_vec();
+
+            let visuals = *ui.style().interact(&response);
+
+            let foreground_color = |enabled: bool| {
+                if enabled {
+                    visuals.text_color()
+                } else {
+                    ui.style().noninteractive().text_color()
+                }
+            };
+
+            let channel_color = |channel_id: usize| {
+                let color = match self
+                    .channel_colors
+                    .as_ref()
+                    .and_then(|channel_colors| channel_colors.get(channel_id))
+                {
+                    Some(&color) => color,
+                    None if self.channel_layout == ChannelLayout::Overlay => {
+                        OVERLAY_COLORS[channel_id % OVERLAY_COLORS.len()]
+                    }
+                    None => visuals.fg_stroke.color,
+                };
+
+                if channels_enabled[channel_id] {
+                    color
+                } else {
+                    color.gamma_multiply(0.4)
+                }
+            };
+
+            let trigger_color = ui.style().visuals.warn_fg_color;
+            let label_color = ui.style().visuals.weak_text_color();
+            let major_grid_color = ui.style().visuals.widgets.noninteractive.bg_stroke.color;
+            let minor_grid_color = major_grid_color.gamma_multiply(0.5);
+
+            ui.painter().rect(
+                rect,
+                visuals.rounding,
+                ui.style().visuals.extreme_bg_color,
+                visuals.fg_stroke,
+            );
+
+            if let Some(buffer) = self.buffer {
+                assert_eq!(buffer.len() % self.channels, 0);
+
+                let channel_buffer_length = buffer.len() / self.channels;
+                assert_eq!(channel_buffer_length.rem_euclid(2), 0);
+
+                if let Some(ref channel_names) = self.channel_names {
+                    assert_eq!(channel_names.len(), self.channels);
+                }
+
+                let window_size = self.window_size.unwrap_or(channel_buffer_length / 2); // Default window size
+                assert!(window_size <= channel_buffer_length);
+                assert_eq!(window_size.rem_euclid(2), 0);
+
+                // Zoom and pan
+                let mut view = WaveformView::load(ui.ctx(), response.id);
+
+                if response.double_clicked() {
+                    view = WaveformView::default();
+                }
+
+                let hovered_waveform_rect = response.hover_pos().and_then(|pointer_pos| {
+                    panes
+                        .iter()
+                        .map(|&(pane_rect, _)| waveform_rect(pane_rect))
+                        .find(|waveform_rect| waveform_rect.contains(pointer_pos))
+                        .map(|waveform_rect| (waveform_rect, pointer_pos))
+                });
+
+                if let Some((waveform_rect, pointer_pos)) = hovered_waveform_rect {
+                    let scroll_delta = ui.input(|input| input.smooth_scroll_delta);
+
+                    if scroll_delta != Vec2::ZERO {
+                        view.zoom_time(
+                            (scroll_delta.y / 200.0).exp(),
+                            (pointer_pos.x - waveform_rect.left()) / waveform_rect.width(),
+                            window_size,
+                        );
+                        view.zoom_amplitude(
+                            (scroll_delta.x / 200.0).exp(),
+                            (waveform_rect.bottom() - pointer_pos.y) / waveform_rect.height(),
+                        );
+
+                        ui.input_mut(|input| input.smooth_scroll_delta = Vec2::ZERO);
+                    }
+                }
+
+                if response.dragged() {
+                    let waveform_size = waveform_rect(panes[0].0).size();
+                    let drag_delta = response.drag_delta();
+
+                    view.pan(
+                        -drag_delta.x / waveform_size.x,
+                        drag_delta.y / waveform_size.y,
+                    );
+                }
+
+                view.store(ui.ctx(), response.id);
+
+                let channel_samples = (0..self.channels)
+                    .map(|channel_id| {
+                        ChannelSamples::new(buffer, self.buffer_layout, self.channels, channel_id)
+                    })
+                    .collect_vec();
+
+                // A cache built before the buffer was resized is ignored
+                let peak_cache = self.peak_cache.filter(|peak_cache| {
+                    (peak_cache.channels() == self.channels)
+                        && (peak_cache.channel_length() == channel_buffer_length)
+                });
+
+                // Trigger
+                let trigger = self
+                    .trigger
+                    .unwrap_or_else(|| Trigger::new(SampleType::ZERO.into()));
+                let source_channel = trigger
+                    .source_channel
+                    .filter(|&channel_id| channel_id < self.channels);
+                let is_trigger_source = |channel_id: usize| {
+                    source_channel.map_or(true, |source_channel| source_channel == channel_id)
+                };
+
+                let pretrigger_length = trigger.pretrigger_length(window_size);
+
+                let find_trigger = |channel_samples: &ChannelSamples<SampleType>| {
+                    trigger.find(
+                        channel_samples.iter(0..channel_samples.len()),
+                        pretrigger_length
+                            ..=(channel_buffer_length - window_size + pretrigger_length),
+                        channel_buffer_length / 2,
+                    )
+                };
+
+                let trigger_points = match source_channel {
+                    Some(source_channel) => {
+                        vec![find_trigger(&channel_samples[source_channel]); self.channels]
+                    }
+                    None => channel_samples.iter().map(find_trigger).collect_vec(),
+                };
+
+                let window_starts = trigger_points
+                    .iter()
+                    .map(|trigger_point| {
+                        trigger_point.map_or(
+                            (channel_buffer_length / 2) - (window_size / 2),
+                            |trigger_point| trigger_point - pretrigger_length,
+                        )
+                    })
+                    .collect_vec();
+
+                // Windows are only copied when they may be shown on later frames
+                let keep_capture = (trigger.mode != TriggerMode::Auto) || (trigger.holdoff > 0.0);
+
+                let capture_windows = || {
+                    Arc::new(
+                        channel_samples
+                            .iter()
+                            .zip(&window_starts)
+                            .map(|(channel_samples, &window_start)| {
+                                channel_samples
+                                    .iter(window_start..(window_start + window_size))
+                                    .collect_vec()
+                            })
+                            .collect_vec(),
+                    )
+                };
+
+                let time = ui.input(|input| input.time);
+
+                let mut trigger_state = ui
+                    .memory(|memory| memory.data.get_temp::<TriggerState>(response.id))
+                    .unwrap_or_default();
+                trigger_state.discard_stale_capture(self.channels, window_size);
+
+                let holdoff_elapsed = trigger_state
+                    .last_trigger_time
+                    .map_or(true, |last_trigger_time| {
+                        time - last_trigger_time >= trigger.holdoff as f64
+                    });
+
+                let displayed_windows = if (trigger.mode == TriggerMode::Single)
+                    && (trigger_state.status == TriggerStatus::Stopped)
+                {
+                    DisplayedWindows::Captured(trigger_state.capture.clone())
+                } else if trigger_points.iter().any(Option::is_some) && holdoff_elapsed {
+                    trigger_state.status = if trigger.mode == TriggerMode::Single {
+                        TriggerStatus::Stopped
+                    } else {
+                        TriggerStatus::Triggered
+                    };
+                    trigger_state.last_trigger_time = Some(time);
+                    trigger_state.capture = keep_capture.then(capture_windows);
+
+                    DisplayedWindows::Live
+                } else if !holdoff_elapsed {
+                    DisplayedWindows::Captured(trigger_state.capture.clone())
+                } else if trigger.mode == TriggerMode::Auto {
+                    trigger_state.status = TriggerStatus::Auto;
+                    DisplayedWindows::Live
+                } else {
+                    trigger_state.status = TriggerStatus::Armed;
+                    DisplayedWindows::Captured(trigger_state.capture.clone())
+                };
+
+                let trigger_status = trigger_state.status;
+
+                ui.memory_mut(|memory| memory.data.insert_temp(response.id, trigger_state));
+
+                let window = |channel_id: usize| match displayed_windows {
+                    DisplayedWindows::Live => Some(WindowSamples::Live {
+                        samples: channel_samples[channel_id],
+                        start: window_starts[channel_id],
+                        len: window_size,
+                        peak_cache: peak_cache.map(|peak_cache| (peak_cache, channel_id)),
+                    }),
+                    DisplayedWindows::Captured(ref capture) => capture
+                        .as_ref()
+                        .and_then(|capture| capture.get(channel_id))
+                        .map(|capture| WindowSamples::Captured(capture)),
+                };
+
+                // Axes
+                let time_range = view.time_range(window_size);
+                let amplitude_range = view.amplitude_range();
+
+                let normalize = |sample: f32| remap(sample, SampleType::DISPLAY_RANGE, -1.0..=1.0);
+
+                // Time axis in seconds (or samples) relative to the trigger point
+                let time_scale = self
+                    .sample_rate
+                    .map_or(1.0, |sample_rate| 1.0 / sample_rate);
+                let to_time = |index: f32| (index - pretrigger_length as f32) * time_scale;
+                let from_time = |time: f32| time / time_scale + pretrigger_length as f32;
+                let format_time = |time: f32, step: f32, extra_decimals: usize| {
+                    if self.sample_rate.is_some() {
+                        view::format_time(time, step, extra_decimals)
+                    } else {
+                        view::format_samples(time, step, extra_decimals)
+                    }
+                };
+
+                let render_pane = |pane_rect: Rect, channel_ids: &[usize]| {
+                    let shows_trigger_source = channel_ids
+                        .iter()
+                        .any(|&channel_id| is_trigger_source(channel_id));
+
+                    // Header
+                    if self.show_header {
+                        let header_rect = header_rect(pane_rect);
+
+                        if let [channel_id] = *channel_ids {
+                            if let Some(channel_name) = channel_name(channel_id) {
+                                ui.painter().text(
+                                    header_rect.center(),
+                                    Align2::CENTER_CENTER,
+                                    channel_name,
+                                    font_id.clone(),
+                                    foreground_color(channels_enabled[channel_id]),
+                                );
+                            }
+
+                            if channels_enabled[channel_id] {
+                                ui.painter().text(
+                                    header_rect.right_center(),
+                                    Align2::RIGHT_CENTER,
+                                    '\u{1F508}',
+                                    font_id.clone(),
+                                    foreground_color(true),
+                                );
+                            }
+                        } else {
+                            for (channel_id, (legend_rect, galley)) in legend.iter().enumerate() {
+                                ui.painter().galley(
+                                    legend_rect.min,
+                                    galley.clone(),
+                                    channel_color(channel_id),
+                                );
+                            }
+                        }
+
+                        if self.trigger.is_some() && shows_trigger_source {
+                            ui.painter().text(
+                                header_rect.left_center(),
+                                Align2::LEFT_CENTER,
+                                trigger_status.to_string(),
+                                font_id.clone(),
+                                trigger_color,
+                            );
+                        }
+                    }
+
+                    // Waveform
+                    let waveform_rect = waveform_rect(pane_rect);
+                    let painter = ui.painter_at(waveform_rect);
+
+                    let index_to_x =
+                        |index: f32| remap(index, time_range.clone(), waveform_rect.x_range());
+                    let normalized_to_y = |normalized: f32| {
+                        remap(
+                            normalized,
+                            amplitude_range.clone(),
+                            waveform_rect.bottom_up_range(),
+                        )
+                    };
+
+                    let visible_time = to_time(*time_range.start())..=to_time(*time_range.end());
+                    let time_step = view::nice_step(
+                        visible_time.end() - visible_time.start(),
+                        waveform_rect.width() / 48.0,
+                    );
+
+                    let (amplitude_step, amplitude_decimals) = self.amplitude_unit.grid_step(
+                        amplitude_range.end() - amplitude_range.start(),
+                        &SampleType::DISPLAY_RANGE,
+                        (waveform_rect.height() / 24.0).max(2.0),
+                    );
+
+                    // Graticule
+                    if self.show_graticule {
+                        let minor_time_step = time_step / view::minor_divisions(time_step) as f32;
+                        let minor_amplitude_step =
+                            amplitude_step / view::minor_divisions(amplitude_step) as f32;
+
+                        if waveform_rect.width() * minor_time_step
+                            / (visible_time.end() - visible_time.start())
+                            >= 4.0
+                        {
+                            for time in view::grid_lines(&visible_time, minor_time_step) {
+                                let x = index_to_x(from_time(time));
+                                painter.vline(x, waveform_rect.y_range(), (1.0, minor_grid_color));
+                            }
+                        }
+
+                        if waveform_rect.height() * minor_amplitude_step
+                            / (amplitude_range.end() - amplitude_range.start())
+                            >= 4.0
+                        {
+                            for normalized in
+                                view::grid_lines(&amplitude_range, minor_amplitude_step)
+                            {
+                                let y = normalized_to_y(normalized);
+                                painter.hline(waveform_rect.x_range(), y, (1.0, minor_grid_color));
+                            }
+                        }
+
+                        for time in view::grid_lines(&visible_time, time_step) {
+                            let x = index_to_x(from_time(time));
+                            painter.vline(x, waveform_rect.y_range(), (1.0, major_grid_color));
+                            painter.text(
+                                pos2(x + 2.0, waveform_rect.bottom()),
+                                Align2::LEFT_BOTTOM,
+                                format_time(time, time_step, 0),
+                                label_font_id.clone(),
+                                label_color,
+                            );
+                        }
+
+                        for normalized in view::grid_lines(&amplitude_range, amplitude_step) {
+                            let y = normalized_to_y(normalized);
+                            painter.hline(waveform_rect.x_range(), y, (1.0, major_grid_color));
+                            painter.text(
+                                pos2(waveform_rect.left() + 2.0, y),
+                                Align2::LEFT_BOTTOM,
+                                self.amplitude_unit.format(
+                                    normalized,
+                                    &SampleType::DISPLAY_RANGE,
+                                    amplitude_decimals,
+                                ),
+                                label_font_id.clone(),
+                                label_color,
+                            );
+                        }
+                    }
+
+                    painter.hline(
+                        waveform_rect.x_range(),
+                        normalized_to_y(0.0),
+                        ui.style().visuals.noninteractive().fg_stroke,
+                    );
+
+                    let column_width = self.waveform_resolution.max(1) as f32;
+                    let samples_per_column = (time_range.end() - time_range.start())
+                        / waveform_rect.width()
+                        * column_width;
+
+                    for &channel_id in channel_ids {
+                        let Some(window) = window(channel_id) else {
+                            continue;
+                        };
+
+                        let waveform_points = if samples_per_column <= 1.0 {
+                            let first_index = (time_range.start().floor().max(0.0)) as usize;
+                            let last_index = (time_range.end().ceil() as usize)
+                                .min(window.len().saturating_sub(1));
+
+                            (first_index..=last_index)
+                                .map(|index| {
+                                    pos2(
+                                        index_to_x(index as f32),
+                                        normalized_to_y(normalize(window.get(index))),
+                                    )
+                                })
+                                .collect_vec()
+                        } else {
+                            // Min/max envelope, one vertical stroke per column
+                            let x_to_index = |x: f32| {
+                                (remap(x, waveform_rect.x_range(), time_range.clone())
+                                    .ceil()
+                                    .max(0.0) as usize)
+                                    .min(window.len())
+                            };
+
+                            (0..(waveform_rect.width() / column_width).ceil() as usize)
+                                .filter_map(|column| {
+                                    let left = waveform_rect.left() + column as f32 * column_width;
+                                    let range = x_to_index(left)..x_to_index(left + column_width);
+
+                                    window
+                                        .min_max(range)
+                                        .map(|min_max| (left + column_width / 2.0, min_max))
+                                })
+                                .flat_map(|(x, (min, max))| {
+                                    [
+                                        pos2(x, normalized_to_y(normalize(max))),
+                                        pos2(x, normalized_to_y(normalize(min))),
+                                    ]
+                                })
+                                .collect_vec()
+                        };
+
+                        painter.add(Shape::line(
+                            waveform_points,
+                            Stroke::new(visuals.fg_stroke.width, channel_color(channel_id)),
+                        ));
+                    }
+
+                    // Trigger markers
+                    if self.trigger.is_some() {
+                        let marker_size = 4.0;
+
+                        let trigger_x = index_to_x(pretrigger_length as f32);
+
+                        painter.add(Shape::convex_polygon(
+                            vec![
+                                pos2(trigger_x - marker_size, waveform_rect.top()),
+                                pos2(trigger_x + marker_size, waveform_rect.top()),
+                                pos2(trigger_x, waveform_rect.top() + marker_size),
+                            ],
+                            trigger_color,
+                            Stroke::NONE,
+                        ));
+
+                        if shows_trigger_source {
+                            let trigger_y = normalized_to_y(normalize(trigger.level));
+
+                            painter.add(Shape::convex_polygon(
+                                vec![
+                                    pos2(waveform_rect.left(), trigger_y - marker_size),
+                                    pos2(waveform_rect.left() + marker_size, trigger_y),
+                                    pos2(waveform_rect.left(), trigger_y + marker_size),
+                                ],
+                                trigger_color,
+                                Stroke::NONE,
+                            ));
+                        }
+                    }
+
+                    // Cursor readout
+                    let cursor_pos = response
+                        .hover_pos()
+                        .filter(|pointer_pos| waveform_rect.contains(*pointer_pos));
+
+                    if let Some(pointer_pos) = cursor_pos.filter(|_| self.show_cursor) {
+                        let index =
+                            remap(pointer_pos.x, waveform_rect.x_range(), time_range.clone())
+                                .round()
+                                .clamp(0.0, window_size.saturating_sub(1) as f32);
+
+                        painter.vline(
+                            index_to_x(index),
+                            waveform_rect.y_range(),
+                            (1.0, label_color),
+                        );
+
+                        let readouts = channel_ids
+                            .iter()
+                            .filter_map(|&channel_id| {
+                                let window = window(channel_id)?;
+                                let normalized = normalize(window.get_checked(index as usize)?);
+
+                                painter.circle_filled(
+                                    pos2(index_to_x(index), normalized_to_y(normalized)),
+                                    2.5,
+                                    channel_color(channel_id),
+                                );
+
+                                Some((
+                                    self.amplitude_unit.format(
+                                        normalized,
+                                        &SampleType::DISPLAY_RANGE,
+                                        amplitude_decimals + 1,
+                                    ),
+                                    channel_color(channel_id),
+                                ))
+                            })
+                            .collect_vec();
+
+                        let time_readout = format_time(to_time(index), time_step, 1);
+
+                        if let [(value_readout, _)] = readouts.as_slice() {
+                            painter.text(
+                                waveform_rect.right_top() + vec2(-2.0, 0.0),
+                                Align2::RIGHT_TOP,
+                                format!("{time_readout}  {value_readout}"),
+                                label_font_id.clone(),
+                                foreground_color(true),
+                            );
+                        } else {
+                            for (line, (text, color)) in [(time_readout, foreground_color(true))]
+                                .into_iter()
+                                .chain(readouts)
+                                .enumerate()
+                            {
+                                painter.text(
+                                    waveform_rect.right_top()
+                                        + vec2(-2.0, line as f32 * label_font_id.size),
+                                    Align2::RIGHT_TOP,
+                                    text,
+                                    label_font_id.clone(),
+                                    color,
+                                );
+                            }
+                        }
+                    }
+                };
+
+                for (pane_index, (pane_rect, channel_ids)) in panes.iter().enumerate() {
+                    render_pane(*pane_rect, channel_ids);
+
+                    if pane_index < panes.len() - 1 {
+                        let separator = if self.channel_layout == ChannelLayout::Stacked {
+                            [pane_rect.left_bottom(), pane_rect.right_bottom()]
+                        } else {
+                            [pane_rect.right_top(), pane_rect.right_bottom()]
+                        };
+
+                        ui.painter()
+                            .line_segment(separator, ui.style().visuals.noninteractive().fg_stroke);
+                    }
+                }
+            }
+        };
+
+        response
+    }
+}
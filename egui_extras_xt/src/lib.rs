@@ -1,4 +1,5 @@
 mod hash;
+mod polygon;
 
 pub mod common;
 
@@ -0,0 +1,235 @@
+use egui::Painter;
+use emath::{Pos2, Vec2};
+use epaint::{Color32, Mesh, Shape, Stroke};
+
+// ----------------------------------------------------------------------------
+
+fn cross(u: Vec2, v: Vec2) -> f32 {
+    u.x * v.y - u.y * v.x
+}
+
+/// Same orientation convention as epaint's tessellator: positive for clockwise
+/// outlines in screen space.
+fn cw_signed_area(points: &[Pos2]) -> f32 {
+    points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(p1, p2)| p1.x * p2.y - p2.x * p1.y)
+        .sum()
+}
+
+fn point_in_triangle(p: Pos2, a: Pos2, b: Pos2, c: Pos2) -> bool {
+    (cross(b - a, p - a) >= 0.0) && (cross(c - b, p - b) >= 0.0) && (cross(a - c, p - c) >= 0.0)
+}
+
+/// Ear clipping triangulation of a simple polygon with clockwise winding
+/// (see [`cw_signed_area`]). Returns indices into `points`.
+fn triangulate(points: &[Pos2]) -> Vec<[u32; 3]> {
+    let mut remaining = (0..points.len()).collect::<Vec<_>>();
+    let mut triangles = Vec::with_capacity(points.len().saturating_sub(2));
+
+    let is_convex =
+        |a: usize, b: usize, c: usize| cross(points[b] - points[a], points[c] - points[b]) > 0.0;
+
+    while remaining.len() > 3 {
+        let n = remaining.len();
+
+        let ear = (0..n).find(|&i| {
+            let (a, b, c) = (
+                remaining[(i + n - 1) % n],
+                remaining[i],
+                remaining[(i + 1) % n],
+            );
+
+            is_convex(a, b, c)
+                && remaining.iter().enumerate().all(|(j, &p)| {
+                    let (prev, next) = (remaining[(j + n - 1) % n], remaining[(j + 1) % n]);
+
+                    [a, b, c].contains(&p)
+                        || [points[a], points[b], points[c]].contains(&points[p])
+                        || is_convex(prev, p, next)
+                        || !point_in_triangle(points[p], points[a], points[b], points[c])
+                })
+        });
+
+        // Self-intersecting or degenerate outline, clip anything to guarantee progress
+        let i = ear.unwrap_or(1);
+
+        triangles.push([
+            remaining[(i + n - 1) % n] as u32,
+            remaining[i] as u32,
+            remaining[(i + 1) % n] as u32,
+        ]);
+        remaining.remove(i);
+    }
+
+    if let [a, b, c] = remaining[..] {
+        triangles.push([a as u32, b as u32, c as u32]);
+    }
+
+    triangles
+}
+
+/// Anti-aliased replacement for [`Shape::convex_polygon`] that also handles
+/// concave outlines.
+///
+/// Works around <https://github.com/emilk/egui/issues/513>.
+pub(crate) fn concave_polygon(
+    painter: &Painter,
+    points: Vec<Pos2>,
+    fill: impl Into<Color32>,
+    stroke: impl Into<Stroke>,
+) -> Shape {
+    let fill = fill.into();
+    let stroke = stroke.into();
+
+    let mut points = points;
+    points.dedup_by(|p1, p2| p1.distance_sq(*p2) < 1e-6);
+    while (points.len() > 1) && (points[0].distance_sq(points[points.len() - 1]) < 1e-6) {
+        points.pop();
+    }
+
+    if points.len() < 3 {
+        return Shape::Noop;
+    }
+
+    if cw_signed_area(&points) < 0.0 {
+        points.reverse();
+    }
+
+    let mut mesh = Mesh::default();
+
+    if fill != Color32::TRANSPARENT {
+        let feathering = painter.ctx().tessellation_options(|options| {
+            if options.feathering {
+                options.feathering_size_in_pixels / painter.ctx().pixels_per_point()
+            } else {
+                0.0
+            }
+        });
+
+        let n = points.len();
+
+        // Feathering follows epaint: solid inner ring, outer ring fading to
+        // the stroke color.
+        for i in 0..n {
+            let (prev, curr, next) = (points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+
+            let n0 = (curr - prev).normalized().rot90();
+            let n1 = (next - curr).normalized().rot90();
+            let normal = (n0 + n1) / 2.0;
+            let normal = normal / normal.length_sq().max(0.25);
+
+            let dm = 0.5 * feathering * normal;
+            mesh.colored_vertex(curr - dm, fill);
+            mesh.colored_vertex(curr + dm, stroke.color);
+        }
+
+        for [a, b, c] in triangulate(&points) {
+            mesh.add_triangle(2 * a, 2 * b, 2 * c);
+        }
+
+        if feathering > 0.0 {
+            for i1 in 0..n as u32 {
+                let i0 = (i1 + n as u32 - 1) % n as u32;
+                mesh.add_triangle(2 * i1, 2 * i0, 2 * i0 + 1);
+                mesh.add_triangle(2 * i0 + 1, 2 * i1 + 1, 2 * i1);
+            }
+        }
+    }
+
+    let mut shapes = vec![Shape::mesh(mesh)];
+    if (stroke.width > 0.0) && (stroke.color != Color32::TRANSPARENT) {
+        shapes.push(Shape::closed_line(points, stroke));
+    }
+
+    Shape::Vec(shapes)
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use emath::pos2;
+
+    fn points(coordinates: &[(f32, f32)]) -> Vec<Pos2> {
+        coordinates.iter().map(|&(x, y)| pos2(x, y)).collect()
+    }
+
+    /// Checks that the triangles have the winding of the outline and cover
+    /// exactly its area, so none of them overlap or stick out.
+    fn assert_triangulation(points: &[Pos2]) {
+        assert!(cw_signed_area(points) > 0.0);
+
+        let triangles = triangulate(points);
+        assert_eq!(triangles.len(), points.len() - 2);
+
+        let mut area = 0.0;
+        for [a, b, c] in triangles {
+            let (a, b, c) = (points[a as usize], points[b as usize], points[c as usize]);
+            let triangle_area = cross(b - a, c - b) / 2.0;
+
+            assert!(triangle_area >= 0.0, "{a:?} {b:?} {c:?}");
+            area += triangle_area;
+        }
+
+        assert!((area - cw_signed_area(points) / 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn convex() {
+        assert_triangulation(&points(&[(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)]));
+        assert_triangulation(&points(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]));
+    }
+
+    #[test]
+    fn concave() {
+        // L shape
+        assert_triangulation(&points(&[
+            (0.0, 0.0),
+            (2.0, 0.0),
+            (2.0, 1.0),
+            (1.0, 1.0),
+            (1.0, 2.0),
+            (0.0, 2.0),
+        ]));
+
+        // Arrow pointing right, reflex vertex at the notch
+        assert_triangulation(&points(&[(0.0, 0.0), (3.0, 1.0), (0.0, 2.0), (1.0, 1.0)]));
+
+        // Comb with several reflex vertices
+        assert_triangulation(&points(&[
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (1.0, 2.0),
+            (2.0, 2.0),
+            (2.0, 0.0),
+            (3.0, 0.0),
+            (3.0, 2.0),
+            (4.0, 2.0),
+            (4.0, 0.0),
+            (5.0, 0.0),
+            (5.0, 3.0),
+            (0.0, 3.0),
+        ]));
+    }
+
+    #[test]
+    fn collinear_points() {
+        assert_triangulation(&points(&[
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (2.0, 0.0),
+            (2.0, 1.0),
+            (1.0, 1.0),
+            (0.0, 1.0),
+        ]));
+    }
+
+    #[test]
+    fn self_intersecting_terminates() {
+        let bow_tie = points(&[(0.0, 0.0), (1.0, 1.0), (1.0, 0.0), (0.0, 1.0)]);
+        assert_eq!(triangulate(&bow_tie).len(), 2);
+    }
+}
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use eframe::egui::{self, pos2, vec2, CentralPanel, Context, Pos2};
+
+use egui_extras_xt::displays::segmented_display::{DisplayGlyph, DisplayImpl};
+use egui_extras_xt::displays::{DisplayStylePreset, SegmentedDisplayWidget};
+
+// ----------------------------------------------------------------------------
+
+/// 5x7 dot-matrix cell, segment `n` is the dot at row `n / 5`, column `n % 5`.
+struct DotMatrix5x7;
+
+impl DisplayImpl for DotMatrix5x7 {
+    fn segment_count(&self) -> usize {
+        35
+    }
+
+    fn glyph(&self, c: char) -> Option<DisplayGlyph> {
+        #[rustfmt::skip]
+        const GLYPH_DATA: &[(char, DisplayGlyph)] = &[
+            (' ', 0x000000000), ('-', 0x0000F8000), ('0', 0x3A33AE62E), ('1', 0x3884210C4),
+            ('2', 0x7C444422E), ('3', 0x3A304111F), ('4', 0x211F4A988), ('5', 0x3A3083C3F),
+            ('6', 0x3A317844C), ('7', 0x08422221F), ('8', 0x3A317462E), ('9', 0x1910F462E),
+            (':', 0x00C6018C0),
+        ];
+
+        GLYPH_DATA
+            .binary_search_by_key(&c, |(k, _)| *k)
+            .ok()
+            .map(|index| GLYPH_DATA[index].1)
+    }
+
+    fn geometry(
+        &self,
+        digit_width: f32,
+        digit_height: f32,
+        _segment_thickness: f32,
+        segment_spacing: f32,
+        _digit_median: f32,
+    ) -> Vec<Vec<Pos2>> {
+        let cell_size = vec2(digit_width / 5.0, digit_height / 7.0);
+        let half_dot = (cell_size / 2.0) - vec2(segment_spacing, segment_spacing);
+
+        (0..35)
+            .map(|segment_index| {
+                let center = pos2(-(digit_width / 2.0), -(digit_height / 2.0))
+                    + cell_size * vec2((segment_index % 5) as f32, (segment_index / 5) as f32)
+                    + cell_size / 2.0;
+
+                vec![
+                    center + vec2(-half_dot.x, -half_dot.y),
+                    center + vec2(half_dot.x, -half_dot.y),
+                    center + vec2(half_dot.x, half_dot.y),
+                    center + vec2(-half_dot.x, half_dot.y),
+                ]
+            })
+            .collect()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[derive(Default)]
+struct CustomDisplayImplDemo;
+
+impl eframe::App for CustomDisplayImplDemo {
+    fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        CentralPanel::default().show(ctx, |ui| {
+            let time = ctx.input(|input| input.time) as u64;
+
+            ui.add(
+                SegmentedDisplayWidget::with_display_impl(Arc::new(DotMatrix5x7))
+                    .style_preset(DisplayStylePreset::Amber)
+                    .show_dots(false)
+                    .show_colons(false)
+                    .show_apostrophes(false)
+                    .push_string(format!(
+                        "{:02}-{:02}-{:02}",
+                        (time / 3600) % 24,
+                        (time / 60) % 60,
+                        time % 60
+                    )),
+            );
+        });
+
+        ctx.request_repaint();
+    }
+}
+
+fn main() -> Result<(), eframe::Error> {
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_inner_size([640.0, 140.0]),
+        ..Default::default()
+    };
+
+    eframe::run_native(
+        "Custom DisplayImpl",
+        options,
+        Box::new(|_| Ok(Box::<CustomDisplayImplDemo>::default())),
+    )
+}
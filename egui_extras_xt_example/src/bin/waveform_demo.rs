@@ -1,28 +1,86 @@
 use std::f32::consts::TAU;
 
-use eframe::egui::{self, DragValue};
+use eframe::egui::{self, Color32, DragValue, Grid};
 
-use egui_extras_xt::displays::{BufferLayout, WaveformDisplayWidget};
+use egui_extras_xt::displays::{
+    AmplitudeUnit, BufferLayout, ChannelLayout, PeakCache, SpectrogramColormap,
+    SpectrogramOrientation, SpectrogramWidget, SpectrumAnalyzerWidget, SpectrumStyle, Trigger,
+    TriggerMode, TriggerSlope, TriggerStatus, VectorscopeMode, VectorscopeWidget,
+    WaveformDisplayWidget, WaveformView, WindowFunction,
+};
+use egui_extras_xt::ui::widgets_from_iter::SelectableValueFromIter;
+use egui_extras_xt::ui::widgets_from_slice::SelectableValueFromSlice;
+use strum::IntoEnumIterator;
 
 const BUFFER_SIZE: usize = 1024;
 const OUTPUT_FREQUENCY: usize = 44100;
+const RECORDING_LENGTH: usize = 30 * OUTPUT_FREQUENCY;
 
 struct WaveformDemoApp {
-    enabled: bool,
+    channels_enabled: [bool; 2],
+    channel_layout: ChannelLayout,
+    channel_colors: [Color32; 2],
     buffer: [f32; BUFFER_SIZE],
     left_frequency: f32,
     right_frequency: f32,
     phase: f32,
+    animate: bool,
+    sweep: bool,
+    vectorscope_mode: VectorscopeMode,
+    persistence: f32,
+    trigger: Trigger,
+    amplitude_unit: AmplitudeUnit,
+    show_graticule: bool,
+    show_cursor: bool,
+    recording_enabled: bool,
+    recording: Vec<f32>,
+    peak_cache: PeakCache,
+    use_peak_cache: bool,
+    spectrum_style: SpectrumStyle,
+    window_function: WindowFunction,
+    peak_hold: bool,
+    spectrogram_colormap: SpectrogramColormap,
+    spectrogram_orientation: SpectrogramOrientation,
 }
 
 impl Default for WaveformDemoApp {
     fn default() -> Self {
+        let recording = (0..RECORDING_LENGTH)
+            .map(|index| {
+                let t = index as f32 / OUTPUT_FREQUENCY as f32;
+                let tremolo = 0.5 + 0.5 * (t * 0.2 * TAU).sin();
+                let decay = (-3.0 * (t % 1.0)).exp();
+                (t * 220.0 * TAU).sin() * tremolo * decay
+            })
+            .collect::<Vec<f32>>();
+
+        let peak_cache = PeakCache::new(&recording, BufferLayout::Interleaved, 1);
+
         let mut tmp = Self {
-            enabled: true,
+            channels_enabled: [true; 2],
+            channel_layout: ChannelLayout::SideBySide,
+            channel_colors: [Color32::YELLOW, Color32::LIGHT_BLUE],
             buffer: [0.0; BUFFER_SIZE],
             left_frequency: 440.0,
             right_frequency: 440.0,
             phase: 0.0,
+            animate: false,
+            sweep: false,
+            vectorscope_mode: VectorscopeMode::MidSide,
+            persistence: 0.25,
+            trigger: Trigger::default(),
+            amplitude_unit: AmplitudeUnit::Volts(1.0),
+            show_graticule: true,
+            show_cursor: true,
+            recording_enabled: true,
+            recording,
+            peak_cache,
+            use_peak_cache: true,
+            spectrum_style: SpectrumStyle::Bars,
+            window_function: WindowFunction::Hann,
+            peak_hold: true,
+            spectrogram_colormap: SpectrogramColormap::Heat,
+            spectrogram_orientation: SpectrogramOrientation::Horizontal,
         };
         tmp.regenerate_buffer();
         tmp
@@ -59,17 +117,282 @@ impl eframe::App for WaveformDemoApp {
                 if ui.add(DragValue::new(&mut self.phase).speed(0.1)).changed() {
                     self.regenerate_buffer();
                 }
+
+                ui.checkbox(&mut self.animate, "Animate");
+                ui.checkbox(&mut self.sweep, "Sweep");
             });
 
+            if self.sweep {
+                let time = ctx.input(|input| input.time);
+                self.right_frequency = 440.0 * 2.0_f32.powf((time * 0.5).sin() as f32);
+                self.regenerate_buffer();
+                ctx.request_repaint();
+            }
+
+            if self.animate {
+                self.phase = (self.phase + 0.01) % 1.0;
+                self.regenerate_buffer();
+                ctx.request_repaint();
+            }
+
             ui.separator();
 
+            let response = ui
+                .horizontal(|ui| {
+                    let response = ui.add(
+                        WaveformDisplayWidget::per_channel(&mut self.channels_enabled)
+                            .track_name("Track #1")
+                            .channel_layout(self.channel_layout)
+                            .channel_colors(&self.channel_colors)
+                            .channel_names(&["Left", "Right"])
+                            .buffer(&self.buffer)
+                            .buffer_layout(BufferLayout::Interleaved)
+                            .width(512.0)
+                            .height(160.0)
+                            .trigger(self.trigger)
+                            .sample_rate(OUTPUT_FREQUENCY as f32)
+                            .amplitude_unit(self.amplitude_unit)
+                            .show_graticule(self.show_graticule)
+                            .show_cursor(self.show_cursor),
+                    );
+
+                    ui.add(
+                        VectorscopeWidget::new(&self.buffer)
+                            .buffer_layout(BufferLayout::Interleaved)
+                            .width(140.0)
+                            .height(160.0)
+                            .mode(self.vectorscope_mode)
+                            .persistence(self.persistence),
+                    );
+
+                    response
+                })
+                .inner;
+
+            ui.separator();
+
+            Grid::new("trigger_properties")
+                .num_columns(2)
+                .spacing([20.0, 10.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Channel layout");
+                    ui.horizontal(|ui| {
+                        ui.selectable_value_from_iter(
+                            &mut self.channel_layout,
+                            ChannelLayout::iter(),
+                        );
+                    });
+                    ui.end_row();
+
+                    ui.label("Channels");
+                    ui.horizontal(|ui| {
+                        for (channel_enabled, channel_color) in self
+                            .channels_enabled
+                            .iter_mut()
+                            .zip(self.channel_colors.iter_mut())
+                        {
+                            ui.checkbox(channel_enabled, "");
+                            ui.color_edit_button_srgba(channel_color);
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Trigger level");
+                    ui.add(
+                        DragValue::new(&mut self.trigger.level)
+                            .speed(0.01)
+                            .range(-1.0..=1.0),
+                    );
+                    ui.end_row();
+
+                    ui.label("Slope");
+                    ui.horizontal(|ui| {
+                        ui.selectable_value_from_iter(
+                            &mut self.trigger.slope,
+                            TriggerSlope::iter(),
+                        );
+                    });
+                    ui.end_row();
+
+                    ui.label("Hysteresis");
+                    ui.add(
+                        DragValue::new(&mut self.trigger.hysteresis)
+                            .speed(0.01)
+                            .range(0.0..=1.0),
+                    );
+                    ui.end_row();
+
+                    ui.label("Holdoff");
+                    ui.add(
+                        DragValue::new(&mut self.trigger.holdoff)
+                            .speed(0.01)
+                            .range(0.0..=5.0)
+                            .suffix(" s"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Mode");
+                    ui.horizontal(|ui| {
+                        ui.selectable_value_from_iter(&mut self.trigger.mode, TriggerMode::iter());
+
+                        if ui.button("Re-arm").clicked() {
+                            Trigger::rearm(ui.ctx(), response.id);
+                        }
+
+                        ui.label(TriggerStatus::load(ui.ctx(), response.id).to_string());
+                    });
+                    ui.end_row();
+
+                    ui.label("Source channel");
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut self.trigger.source_channel, None, "Each");
+                        ui.selectable_value(&mut self.trigger.source_channel, Some(0), "Left");
+                        ui.selectable_value(&mut self.trigger.source_channel, Some(1), "Right");
+                    });
+                    ui.end_row();
+
+                    ui.label("Position");
+                    ui.add(
+                        DragValue::new(&mut self.trigger.position)
+                            .speed(0.01)
+                            .range(0.0..=1.0),
+                    );
+                    ui.end_row();
+
+                    ui.label("Amplitude unit");
+                    ui.horizontal(|ui| {
+                        ui.selectable_value_from_slice(
+                            &mut self.amplitude_unit,
+                            &[
+                                AmplitudeUnit::Raw,
+                                AmplitudeUnit::Volts(1.0),
+                                AmplitudeUnit::Dbfs,
+                            ],
+                        );
+                    });
+                    ui.end_row();
+
+                    ui.label("Graticule");
+                    ui.checkbox(&mut self.show_graticule, "");
+                    ui.end_row();
+
+                    ui.label("Cursor");
+                    ui.checkbox(&mut self.show_cursor, "");
+                    ui.end_row();
+
+                    ui.label("Vectorscope");
+                    ui.horizontal(|ui| {
+                        ui.selectable_value_from_iter(
+                            &mut self.vectorscope_mode,
+                            VectorscopeMode::iter(),
+                        );
+                        ui.label("Persistence");
+                        ui.add(
+                            DragValue::new(&mut self.persistence)
+                                .speed(0.01)
+                                .range(0.0..=2.0)
+                                .suffix(" s"),
+                        );
+                    });
+                    ui.end_row();
+
+                    ui.label("Spectrum");
+                    ui.horizontal(|ui| {
+                        ui.selectable_value_from_iter(
+                            &mut self.spectrum_style,
+                            SpectrumStyle::iter(),
+                        );
+                        ui.checkbox(&mut self.peak_hold, "Peak hold");
+                    });
+                    ui.end_row();
+
+                    ui.label("Window function");
+                    ui.horizontal(|ui| {
+                        ui.selectable_value_from_iter(
+                            &mut self.window_function,
+                            WindowFunction::iter(),
+                        );
+                    });
+                    ui.end_row();
+
+                    ui.label("Spectrogram");
+                    ui.horizontal(|ui| {
+                        ui.selectable_value_from_iter(
+                            &mut self.spectrogram_colormap,
+                            SpectrogramColormap::iter(),
+                        );
+                        ui.separator();
+                        ui.selectable_value_from_iter(
+                            &mut self.spectrogram_orientation,
+                            SpectrogramOrientation::iter(),
+                        );
+                    });
+                    ui.end_row();
+
+                    ui.label("Peak cache");
+                    ui.checkbox(&mut self.use_peak_cache, "");
+                    ui.end_row();
+
+                    ui.label("View");
+                    ui.horizontal(|ui| {
+                        let view = WaveformView::load(ui.ctx(), response.id);
+                        ui.label(format!(
+                            "Zoom {:.1}x / {:.1}x",
+                            view.time_zoom(),
+                            view.amplitude_zoom()
+                        ));
+
+                        if ui.button("Reset").clicked() {
+                            WaveformView::reset(ui.ctx(), response.id);
+                        }
+                    });
+                    ui.end_row();
+                });
+
+            ui.separator();
+
+            let recording_display = WaveformDisplayWidget::new(&mut self.recording_enabled)
+                .track_name("Recording")
+                .buffer(&self.recording)
+                .window_size(RECORDING_LENGTH)
+                .width(512.0)
+                .height(96.0)
+                .sample_rate(OUTPUT_FREQUENCY as f32)
+                .amplitude_unit(self.amplitude_unit);
+
+            if self.use_peak_cache {
+                ui.add(recording_display.peak_cache(&self.peak_cache));
+            } else {
+                ui.add(recording_display);
+            }
+
+            ui.separator();
+
+            ui.add(
+                SpectrumAnalyzerWidget::new(&self.buffer)
+                    .buffer_layout(BufferLayout::Interleaved)
+                    .channels(2)
+                    .sample_rate(OUTPUT_FREQUENCY as f32)
+                    .fft_size(BUFFER_SIZE / 2)
+                    .window_function(self.window_function)
+                    .style(self.spectrum_style)
+                    .peak_hold(self.peak_hold)
+                    .width(512.0)
+                    .height(128.0),
+            );
+
             ui.add(
-                WaveformDisplayWidget::new(&mut self.enabled)
-                    .track_name("Track #1")
+                SpectrogramWidget::new(&self.buffer)
+                    .buffer_layout(BufferLayout::Interleaved)
                     .channels(2)
-                    .channel_names(&["Left", "Right"])
-                    .buffer(&self.buffer)
-                    .buffer_layout(BufferLayout::Interleaved),
+                    .sample_rate(OUTPUT_FREQUENCY as f32)
+                    .fft_size(BUFFER_SIZE / 2)
+                    .window_function(self.window_function)
+                    .colormap(self.spectrogram_colormap)
+                    .orientation(self.spectrogram_orientation)
+                    .width(512.0)
+                    .height(128.0),
             );
 
             ui.separator();
@@ -82,7 +405,7 @@ impl eframe::App for WaveformDemoApp {
 
 fn main() -> Result<(), eframe::Error> {
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default().with_inner_size([640.0, 480.0]),
+        viewport: egui::ViewportBuilder::default().with_inner_size([720.0, 1200.0]),
         ..Default::default()
     };
 
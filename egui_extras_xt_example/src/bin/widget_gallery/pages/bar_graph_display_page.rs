@@ -0,0 +1,180 @@
+use std::ops::RangeInclusive;
+
+use eframe::egui::{DragValue, Grid, Slider, Ui};
+use egui_extras_xt::displays::{
+    BarGraphDisplay, BarGraphOrientation, BarGraphScale, BarGraphZone, DisplayStyle,
+    DisplayStylePreset,
+};
+use egui_extras_xt::ui::drag_rangeinclusive::DragRangeInclusive;
+use egui_extras_xt::ui::widgets_from_iter::SelectableValueFromIter;
+use strum::IntoEnumIterator;
+
+use crate::pages::ui::display_style_ui;
+use crate::pages::PageImpl;
+
+pub struct BarGraphDisplayPage {
+    value: f32,
+    simulate_signal: bool,
+    range: RangeInclusive<f32>,
+    scale: BarGraphScale,
+    orientation: BarGraphOrientation,
+    segment_count: usize,
+    segment_spacing: f32,
+    length: f32,
+    thickness: f32,
+    zones: bool,
+    yellow_start: f32,
+    red_start: f32,
+    peak_hold: bool,
+    peak_hold_time: f32,
+    peak_decay_rate: f32,
+    style: DisplayStyle,
+    style_preset: DisplayStylePreset,
+    animated: bool,
+}
+
+impl Default for BarGraphDisplayPage {
+    fn default() -> BarGraphDisplayPage {
+        BarGraphDisplayPage {
+            value: 0.5,
+            simulate_signal: true,
+            range: -60.0..=0.0,
+            scale: BarGraphScale::Decibel,
+            orientation: BarGraphOrientation::Vertical,
+            segment_count: 20,
+            segment_spacing: 0.25,
+            length: 160.0,
+            thickness: 16.0,
+            zones: true,
+            yellow_start: -12.0,
+            red_start: -3.0,
+            peak_hold: true,
+            peak_hold_time: 1.5,
+            peak_decay_rate: 0.5,
+            style: DisplayStylePreset::Default.style(),
+            style_preset: DisplayStylePreset::Default,
+            animated: true,
+        }
+    }
+}
+
+impl PageImpl for BarGraphDisplayPage {
+    fn ui(&mut self, ui: &mut Ui) {
+        if self.simulate_signal {
+            let time = ui.input(|input| input.time);
+            let envelope = 0.5 + 0.5 * (time * 0.7).sin() * (time * 2.3).cos();
+            let noise = 0.5 + 0.5 * (time * 37.0).sin() * (time * 11.0).sin();
+            self.value = (envelope * noise) as f32;
+            ui.ctx().request_repaint();
+        }
+
+        ui.horizontal(|ui| {
+            for channel in 0..2 {
+                ui.add(
+                    BarGraphDisplay::new(self.value * (1.0 - 0.2 * channel as f32))
+                        .range(self.range.clone())
+                        .scale(self.scale)
+                        .orientation(self.orientation)
+                        .segment_count(self.segment_count)
+                        .segment_spacing(self.segment_spacing)
+                        .length(self.length)
+                        .thickness(self.thickness)
+                        .zones(if self.zones {
+                            BarGraphZone::traffic_light(self.yellow_start, self.red_start)
+                        } else {
+                            Vec::new()
+                        })
+                        .peak_hold(self.peak_hold)
+                        .peak_hold_time(self.peak_hold_time)
+                        .peak_decay_rate(self.peak_decay_rate)
+                        .style(self.style)
+                        .animated(self.animated),
+                );
+            }
+        });
+        ui.separator();
+
+        Grid::new("bar_graph_display_properties")
+            .num_columns(2)
+            .spacing([20.0, 10.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Value");
+                ui.horizontal(|ui| {
+                    ui.add_enabled(
+                        !self.simulate_signal,
+                        Slider::new(&mut self.value, 0.0..=1.0),
+                    );
+                    ui.checkbox(&mut self.simulate_signal, "Simulate signal");
+                });
+                ui.end_row();
+
+                ui.label("Scale");
+                ui.horizontal(|ui| {
+                    ui.selectable_value_from_iter(&mut self.scale, BarGraphScale::iter());
+                });
+                ui.end_row();
+
+                ui.label("Range");
+                ui.drag_rangeinclusive(&mut self.range);
+                ui.end_row();
+
+                ui.label("Orientation");
+                ui.horizontal(|ui| {
+                    ui.selectable_value_from_iter(
+                        &mut self.orientation,
+                        BarGraphOrientation::iter(),
+                    );
+                });
+                ui.end_row();
+
+                ui.label("Segment count");
+                ui.add(DragValue::new(&mut self.segment_count).range(1..=100));
+                ui.end_row();
+
+                ui.label("Segment spacing");
+                ui.add(Slider::new(&mut self.segment_spacing, 0.0..=0.9));
+                ui.end_row();
+
+                ui.label("Length");
+                ui.add(DragValue::new(&mut self.length));
+                ui.end_row();
+
+                ui.label("Thickness");
+                ui.add(DragValue::new(&mut self.thickness));
+                ui.end_row();
+
+                ui.label("Zones");
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.zones, "");
+                    ui.add_enabled_ui(self.zones, |ui| {
+                        ui.label("Yellow");
+                        ui.add(DragValue::new(&mut self.yellow_start));
+                        ui.label("Red");
+                        ui.add(DragValue::new(&mut self.red_start));
+                    });
+                });
+                ui.end_row();
+
+                ui.label("Peak hold");
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.peak_hold, "");
+                    ui.add_enabled_ui(self.peak_hold, |ui| {
+                        ui.label("Time");
+                        ui.add(DragValue::new(&mut self.peak_hold_time).speed(0.1));
+                        ui.label("Decay");
+                        ui.add(DragValue::new(&mut self.peak_decay_rate).speed(0.1));
+                    });
+                });
+                ui.end_row();
+
+                ui.label("Style");
+                display_style_ui(ui, &mut self.style, &mut self.style_preset);
+                ui.end_row();
+
+                ui.label("Animated");
+                ui.checkbox(&mut self.animated, "");
+                ui.end_row();
+            });
+    }
+}
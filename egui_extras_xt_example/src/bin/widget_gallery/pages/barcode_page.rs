@@ -1,6 +1,6 @@
 use eframe::egui::{DragValue, Grid, Ui};
 use eframe::epaint::Color32;
-use egui_extras_xt::barcodes::{BarcodeKind, BarcodeWidget};
+use egui_extras_xt::barcodes::{BarcodeKind, BarcodeWidget, ErrorPlaceholder};
 use egui_extras_xt::ui::widgets_from_iter::ComboBoxFromIter;
 use strum::IntoEnumIterator;
 
@@ -18,6 +18,9 @@ pub struct BarcodePage {
     label_top_margin: f32,
     foreground_color: Color32,
     background_color: Color32,
+    error_placeholder: ErrorPlaceholder,
+    append_check_digit: bool,
+    filter_input: bool,
 }
 
 impl Default for BarcodePage {
@@ -34,6 +37,9 @@ impl Default for BarcodePage {
             label_top_margin: 4.0,
             foreground_color: Color32::BLACK,
             background_color: Color32::WHITE,
+            error_placeholder: ErrorPlaceholder::Detailed,
+            append_check_digit: false,
+            filter_input: false,
         }
     }
 }
@@ -51,7 +57,9 @@ impl PageImpl for BarcodePage {
                 .label_height(self.label_height)
                 .label_top_margin(self.label_top_margin)
                 .foreground_color(self.foreground_color)
-                .background_color(self.background_color),
+                .background_color(self.background_color)
+                .error_placeholder(self.error_placeholder)
+                .append_check_digit(self.append_check_digit),
         );
         ui.separator();
 
@@ -61,13 +69,35 @@ impl PageImpl for BarcodePage {
             .striped(true)
             .show(ui, |ui| {
                 ui.label("Value");
-                ui.text_edit_singleline(&mut self.value);
+                ui.horizontal(|ui| {
+                    if ui.text_edit_singleline(&mut self.value).changed() && self.filter_input {
+                        self.value.retain(|c| self.barcode_kind.is_valid_char(c));
+                    }
+                    if let Err(error) = self.barcode_kind.validate(&self.value) {
+                        ui.colored_label(ui.style().visuals.error_fg_color, error.to_string());
+                    }
+                });
+                ui.end_row();
+
+                ui.label("Filter input");
+                ui.checkbox(&mut self.filter_input, "");
                 ui.end_row();
 
                 ui.label("Barcode kind");
                 ui.combobox_from_iter("", &mut self.barcode_kind, BarcodeKind::iter());
                 ui.end_row();
 
+                ui.label("Append check digit");
+                ui.add_enabled_ui(self.barcode_kind.has_check_digit(), |ui| {
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut self.append_check_digit, "");
+                        if let Some(check_digit) = self.barcode_kind.check_digit(&self.value) {
+                            ui.label(format!("({check_digit})"));
+                        }
+                    });
+                });
+                ui.end_row();
+
                 ui.label("Bar width");
                 ui.add(DragValue::new(&mut self.bar_width));
                 ui.end_row();
@@ -103,6 +133,10 @@ impl PageImpl for BarcodePage {
                 ui.label("Background color");
                 ui.color_edit_button_srgba(&mut self.background_color);
                 ui.end_row();
+
+                ui.label("Error placeholder");
+                ui.combobox_from_iter("", &mut self.error_placeholder, ErrorPlaceholder::iter());
+                ui.end_row();
             });
     }
 }
@@ -0,0 +1,123 @@
+use eframe::egui::{DragValue, Grid, Ui};
+use egui_extras_xt::displays::character_display::{CharacterPattern, CursorMode};
+use egui_extras_xt::displays::{CharacterDisplayWidget, DisplayStyle, DisplayStylePreset};
+use egui_extras_xt::ui::widgets_from_iter::SelectableValueFromIter;
+use strum::IntoEnumIterator;
+
+use crate::pages::ui::display_style_ui;
+use crate::pages::PageImpl;
+
+#[rustfmt::skip]
+const CGRAM_HEART: CharacterPattern = [
+    0b00000,
+    0b01010,
+    0b11111,
+    0b11111,
+    0b01110,
+    0b00100,
+    0b00000,
+    0b00000,
+];
+
+#[rustfmt::skip]
+const CGRAM_BELL: CharacterPattern = [
+    0b00100,
+    0b01110,
+    0b01110,
+    0b01110,
+    0b11111,
+    0b00000,
+    0b00100,
+    0b00000,
+];
+
+pub struct CharacterDisplayPage {
+    text: String,
+    columns: usize,
+    rows: usize,
+    cursor_column: usize,
+    cursor_row: usize,
+    cursor_mode: CursorMode,
+    dot_size: f32,
+    dot_spacing: f32,
+    style: DisplayStyle,
+    style_preset: DisplayStylePreset,
+}
+
+impl Default for CharacterDisplayPage {
+    fn default() -> CharacterDisplayPage {
+        CharacterDisplayPage {
+            text: "egui_extras_xt \u{0}\nHD44780 LCD \u{1}".to_owned(),
+            columns: 16,
+            rows: 2,
+            cursor_column: 13,
+            cursor_row: 1,
+            cursor_mode: CursorMode::UnderlineBlink,
+            dot_size: 4.0,
+            dot_spacing: 0.15,
+            style: DisplayStylePreset::NintendoGameBoy.style(),
+            style_preset: DisplayStylePreset::NintendoGameBoy,
+        }
+    }
+}
+
+impl PageImpl for CharacterDisplayPage {
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.add(
+            CharacterDisplayWidget::new(self.columns, self.rows)
+                .text(&self.text)
+                .cgram(0, CGRAM_HEART)
+                .cgram(1, CGRAM_BELL)
+                .cursor_position(self.cursor_column, self.cursor_row)
+                .cursor_mode(self.cursor_mode)
+                .dot_size(self.dot_size)
+                .dot_spacing(self.dot_spacing)
+                .style(self.style),
+        );
+        ui.separator();
+
+        Grid::new("character_display_properties")
+            .num_columns(2)
+            .spacing([20.0, 10.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Text");
+                ui.text_edit_multiline(&mut self.text)
+                    .on_hover_text("\\u{0}..\\u{7} are CGRAM characters");
+                ui.end_row();
+
+                ui.label("Size");
+                ui.horizontal(|ui| {
+                    ui.add(DragValue::new(&mut self.columns).range(1..=40));
+                    ui.label("\u{D7}");
+                    ui.add(DragValue::new(&mut self.rows).range(1..=4));
+                });
+                ui.end_row();
+
+                ui.label("Cursor position");
+                ui.horizontal(|ui| {
+                    ui.add(DragValue::new(&mut self.cursor_column));
+                    ui.add(DragValue::new(&mut self.cursor_row));
+                });
+                ui.end_row();
+
+                ui.label("Cursor mode");
+                ui.horizontal(|ui| {
+                    ui.selectable_value_from_iter(&mut self.cursor_mode, CursorMode::iter());
+                });
+                ui.end_row();
+
+                ui.label("Dot size");
+                ui.add(DragValue::new(&mut self.dot_size));
+                ui.end_row();
+
+                ui.label("Dot spacing");
+                ui.add(DragValue::new(&mut self.dot_spacing).speed(0.01));
+                ui.end_row();
+
+                ui.label("Style");
+                display_style_ui(ui, &mut self.style, &mut self.style_preset);
+                ui.end_row();
+            });
+    }
+}
@@ -1,6 +1,8 @@
 use eframe::egui::{DragValue, Grid, Ui};
 use eframe::epaint::Color32;
-use egui_extras_xt::barcodes::DataMatrixWidget;
+use egui_extras_xt::barcodes::{DataMatrixWidget, ErrorPlaceholder};
+use egui_extras_xt::ui::widgets_from_iter::ComboBoxFromIter;
+use strum::IntoEnumIterator;
 
 use crate::pages::PageImpl;
 
@@ -10,6 +12,7 @@ pub struct DataMatrixPage {
     quiet_zone: usize,
     foreground_color: Color32,
     background_color: Color32,
+    error_placeholder: ErrorPlaceholder,
 }
 
 impl Default for DataMatrixPage {
@@ -20,6 +23,7 @@ impl Default for DataMatrixPage {
             quiet_zone: 1,
             foreground_color: Color32::BLACK,
             background_color: Color32::WHITE,
+            error_placeholder: ErrorPlaceholder::Detailed,
         }
     }
 }
@@ -31,7 +35,8 @@ impl PageImpl for DataMatrixPage {
                 .module_size(self.module_size)
                 .quiet_zone(self.quiet_zone)
                 .foreground_color(self.foreground_color)
-                .background_color(self.background_color),
+                .background_color(self.background_color)
+                .error_placeholder(self.error_placeholder),
         );
         ui.separator();
 
@@ -59,6 +64,10 @@ impl PageImpl for DataMatrixPage {
                 ui.label("Background color");
                 ui.color_edit_button_srgba(&mut self.background_color);
                 ui.end_row();
+
+                ui.label("Error placeholder");
+                ui.combobox_from_iter("", &mut self.error_placeholder, ErrorPlaceholder::iter());
+                ui.end_row();
             });
     }
 }
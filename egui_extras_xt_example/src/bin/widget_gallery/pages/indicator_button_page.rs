@@ -1,6 +1,7 @@
-use eframe::egui::{DragValue, Grid, Ui};
+use eframe::egui::{Color32, DragValue, Grid, Ui};
 use egui_extras_xt::displays::{
-    DisplayStyle, DisplayStylePreset, IndicatorButton, IndicatorButtonBehavior,
+    DisplayStyle, DisplayStylePreset, IndicatorButton, IndicatorButtonBehavior, IndicatorState,
+    LedBlinkPattern,
 };
 use egui_extras_xt::ui::widgets_from_iter::SelectableValueFromIter;
 use strum::IntoEnumIterator;
@@ -8,6 +9,13 @@ use strum::IntoEnumIterator;
 use crate::pages::ui::display_style_ui;
 use crate::pages::PageImpl;
 
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum RecordState {
+    Off,
+    Armed,
+    Recording,
+}
+
 pub struct IndicatorButtonPage {
     value: bool,
     width: f32,
@@ -19,6 +27,10 @@ pub struct IndicatorButtonPage {
     interactive: bool,
     margin: f32,
     behavior: IndicatorButtonBehavior,
+    pulse_duration: f32,
+    transport: usize,
+    record_state: RecordState,
+    level: usize,
 }
 
 impl Default for IndicatorButtonPage {
@@ -34,6 +46,10 @@ impl Default for IndicatorButtonPage {
             interactive: true,
             margin: 0.2,
             behavior: IndicatorButtonBehavior::Toggle,
+            pulse_duration: 0.25,
+            transport: 0,
+            record_state: RecordState::Off,
+            level: 0,
         }
     }
 }
@@ -49,10 +65,66 @@ impl PageImpl for IndicatorButtonPage {
                 .animated(self.animated)
                 .interactive(self.interactive)
                 .margin(self.margin)
-                .behavior(self.behavior),
+                .behavior(self.behavior)
+                .pulse_duration(self.pulse_duration),
         );
         ui.separator();
 
+        ui.horizontal(|ui| {
+            for (index, label) in ["STOP", "PLAY", "REC", "REW", "FF"].into_iter().enumerate() {
+                ui.add(
+                    IndicatorButton::radio(&mut self.transport, index)
+                        .width(self.width)
+                        .height(self.height)
+                        .label(label)
+                        .style(self.style)
+                        .animated(self.animated),
+                );
+            }
+        });
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.add(
+                IndicatorButton::enum_state(
+                    &mut self.record_state,
+                    vec![
+                        (RecordState::Off, IndicatorState::OFF),
+                        (
+                            RecordState::Armed,
+                            IndicatorState::ON
+                                .color(Color32::RED)
+                                .blink_pattern(LedBlinkPattern::Fast),
+                        ),
+                        (
+                            RecordState::Recording,
+                            IndicatorState::ON.color(Color32::RED),
+                        ),
+                    ],
+                )
+                .width(self.width)
+                .height(self.height)
+                .label("ARM")
+                .style(self.style)
+                .animated(self.animated),
+            );
+
+            ui.add(
+                IndicatorButton::multi_state(
+                    &mut self.level,
+                    (0..4)
+                        .map(|level| IndicatorState::new(level as f32 / 3.0))
+                        .collect(),
+                )
+                .width(self.width)
+                .height(self.height)
+                .label("LEVEL")
+                .style(self.style)
+                .animated(self.animated),
+            );
+        });
+        ui.separator();
+
         Grid::new("indicator_button_properties")
             .num_columns(2)
             .spacing([20.0, 10.0])
@@ -98,6 +170,14 @@ impl PageImpl for IndicatorButtonPage {
                     );
                 });
                 ui.end_row();
+
+                ui.label("Pulse duration");
+                ui.add(
+                    DragValue::new(&mut self.pulse_duration)
+                        .speed(0.01)
+                        .range(0.0..=5.0),
+                );
+                ui.end_row();
             });
     }
 }
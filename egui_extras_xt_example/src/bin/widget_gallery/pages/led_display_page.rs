@@ -1,12 +1,33 @@
 use std::ops::RangeInclusive;
 
-use eframe::egui::{DragValue, Grid, Ui};
-use egui_extras_xt::displays::{DisplayStyle, DisplayStylePreset, LedDisplay};
+use eframe::egui::{Color32, DragValue, Grid, Slider, Ui};
+use egui_extras_xt::common::Orientation;
+use egui_extras_xt::displays::{
+    DisplayStyle, DisplayStylePreset, LedBlinkPattern, LedDisplay, LedLabelPosition, LedShape,
+};
 use egui_extras_xt::ui::drag_rangeinclusive::DragRangeInclusive;
+use egui_extras_xt::ui::widgets_from_iter::{ComboBoxFromIter, SelectableValueFromIter};
+use egui_extras_xt::ui::widgets_from_slice::SelectableValueFromSlice;
+use strum::{Display, EnumIter, IntoEnumIterator};
 
 use crate::pages::ui::display_style_ui;
 use crate::pages::PageImpl;
 
+#[derive(Clone, Copy, Display, EnumIter, Eq, PartialEq)]
+enum LedColorMode {
+    #[strum(to_string = "Style")]
+    Style,
+
+    #[strum(to_string = "RGB")]
+    Rgb,
+
+    #[strum(to_string = "Hue")]
+    Hue,
+
+    #[strum(to_string = "Bi-color")]
+    Bicolor,
+}
+
 pub struct LedDisplayPage {
     value: f32,
     diameter: f32,
@@ -14,6 +35,18 @@ pub struct LedDisplayPage {
     range: RangeInclusive<f32>,
     style: DisplayStyle,
     style_preset: DisplayStylePreset,
+    color_mode: LedColorMode,
+    color: Color32,
+    hue: f32,
+    bicolor: (Color32, Color32),
+    bicolor_mix: f32,
+    blink_pattern: LedBlinkPattern,
+    shape: LedShape,
+    label: String,
+    label_position: LedLabelPosition,
+    interactive: bool,
+    click_count: usize,
+    history_length: usize,
     animated: bool,
 }
 
@@ -26,6 +59,18 @@ impl Default for LedDisplayPage {
             range: 0.0..=1.0,
             style: DisplayStylePreset::Default.style(),
             style_preset: DisplayStylePreset::Default,
+            color_mode: LedColorMode::Style,
+            color: Color32::from_rgb(0x00, 0x80, 0xFF),
+            hue: 0.0,
+            bicolor: (Color32::RED, Color32::GREEN),
+            bicolor_mix: 0.5,
+            blink_pattern: LedBlinkPattern::Steady,
+            shape: LedShape::Circle,
+            label: "Alarm".to_owned(),
+            label_position: LedLabelPosition::Right,
+            interactive: true,
+            click_count: 0,
+            history_length: 8,
             animated: true,
         }
     }
@@ -33,14 +78,36 @@ impl Default for LedDisplayPage {
 
 impl PageImpl for LedDisplayPage {
     fn ui(&mut self, ui: &mut Ui) {
-        ui.add(
-            LedDisplay::new(self.value)
-                .diameter(self.diameter)
-                .padding(self.padding)
-                .range(self.range.clone())
-                .style(self.style)
-                .animated(self.animated),
-        );
+        let led_display = LedDisplay::new(self.value)
+            .diameter(self.diameter)
+            .padding(self.padding)
+            .range(self.range.clone())
+            .style(self.style)
+            .blink_pattern(self.blink_pattern)
+            .shape(self.shape)
+            .label_position(self.label_position)
+            .interactive(self.interactive)
+            .history_length(self.history_length)
+            .animated(self.animated);
+
+        let led_display = if self.label.is_empty() {
+            led_display
+        } else {
+            led_display.label(&self.label)
+        };
+
+        let response = ui.add(match self.color_mode {
+            LedColorMode::Style => led_display,
+            LedColorMode::Rgb => led_display.color(self.color),
+            LedColorMode::Hue => led_display.hue(self.hue),
+            LedColorMode::Bicolor => {
+                led_display.bicolor(self.bicolor.0, self.bicolor.1, self.bicolor_mix)
+            }
+        });
+
+        if response.clicked() {
+            self.click_count += 1;
+        }
         ui.separator();
 
         Grid::new("led_display_properties")
@@ -68,6 +135,85 @@ impl PageImpl for LedDisplayPage {
                 display_style_ui(ui, &mut self.style, &mut self.style_preset);
                 ui.end_row();
 
+                ui.label("Color");
+                ui.horizontal(|ui| {
+                    ui.selectable_value_from_iter(&mut self.color_mode, LedColorMode::iter());
+                    ui.separator();
+
+                    match self.color_mode {
+                        LedColorMode::Style => {}
+                        LedColorMode::Rgb => {
+                            ui.color_edit_button_srgba(&mut self.color);
+                        }
+                        LedColorMode::Hue => {
+                            ui.add(Slider::new(&mut self.hue, 0.0..=1.0));
+                        }
+                        LedColorMode::Bicolor => {
+                            ui.color_edit_button_srgba(&mut self.bicolor.0);
+                            ui.color_edit_button_srgba(&mut self.bicolor.1);
+                            ui.add(Slider::new(&mut self.bicolor_mix, 0.0..=1.0));
+                        }
+                    }
+                });
+                ui.end_row();
+
+                ui.label("Blink pattern");
+                ui.horizontal(|ui| {
+                    ui.push_id("blink_pattern_combo", |ui| {
+                        ui.combobox_from_iter("", &mut self.blink_pattern, LedBlinkPattern::iter());
+                    });
+
+                    if let LedBlinkPattern::Custom {
+                        ref mut on,
+                        ref mut off,
+                    } = self.blink_pattern
+                    {
+                        ui.label("On");
+                        ui.add(DragValue::new(on).speed(0.01).range(0.0..=10.0));
+                        ui.label("Off");
+                        ui.add(DragValue::new(off).speed(0.01).range(0.0..=10.0));
+                    }
+                });
+                ui.end_row();
+
+                ui.label("Shape");
+                ui.horizontal(|ui| {
+                    ui.selectable_value_from_slice(
+                        &mut self.shape,
+                        &[
+                            LedShape::Circle,
+                            LedShape::Square,
+                            LedShape::Bar,
+                            LedShape::Arrow(Orientation::Top),
+                            LedShape::Arrow(Orientation::Right),
+                            LedShape::Arrow(Orientation::Bottom),
+                            LedShape::Arrow(Orientation::Left),
+                        ],
+                    );
+                });
+                ui.end_row();
+
+                ui.label("Label");
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut self.label);
+                    ui.selectable_value_from_iter(
+                        &mut self.label_position,
+                        LedLabelPosition::iter(),
+                    );
+                });
+                ui.end_row();
+
+                ui.label("Interactive");
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.interactive, "");
+                    ui.label(format!("Clicked {} times", self.click_count));
+                });
+                ui.end_row();
+
+                ui.label("History length");
+                ui.add(DragValue::new(&mut self.history_length).range(0..=64));
+                ui.end_row();
+
                 ui.label("Animated");
                 ui.checkbox(&mut self.animated, "");
                 ui.end_row();
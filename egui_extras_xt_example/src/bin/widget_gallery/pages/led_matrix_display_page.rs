@@ -0,0 +1,137 @@
+use eframe::egui::{Color32, DragValue, Grid, Slider, TextEdit, Ui};
+use eframe::epaint::Hsva;
+use egui_extras_xt::displays::{DisplayStyle, DisplayStylePreset, LedMatrixDisplay, LedPixelShape};
+use egui_extras_xt::ui::widgets_from_iter::SelectableValueFromIter;
+use strum::{Display, EnumIter, IntoEnumIterator};
+
+use crate::pages::ui::display_style_ui;
+use crate::pages::PageImpl;
+
+#[derive(Clone, Copy, Display, EnumIter, Eq, PartialEq)]
+enum LedMatrixContent {
+    #[strum(to_string = "Text")]
+    Text,
+
+    #[strum(to_string = "Plasma")]
+    Plasma,
+
+    #[strum(to_string = "Rainbow")]
+    Rainbow,
+}
+
+pub struct LedMatrixDisplayPage {
+    content: LedMatrixContent,
+    columns: usize,
+    rows: usize,
+    text: String,
+    scroll_speed: f32,
+    pixel_size: f32,
+    pixel_spacing: f32,
+    pixel_shape: LedPixelShape,
+    style: DisplayStyle,
+    style_preset: DisplayStylePreset,
+}
+
+impl Default for LedMatrixDisplayPage {
+    fn default() -> LedMatrixDisplayPage {
+        LedMatrixDisplayPage {
+            content: LedMatrixContent::Text,
+            columns: 64,
+            rows: 16,
+            text: "Hello, LED matrix!".to_owned(),
+            scroll_speed: 20.0,
+            pixel_size: 6.0,
+            pixel_spacing: 0.25,
+            pixel_shape: LedPixelShape::Round,
+            style: DisplayStylePreset::DeLoreanRed.style(),
+            style_preset: DisplayStylePreset::DeLoreanRed,
+        }
+    }
+}
+
+impl PageImpl for LedMatrixDisplayPage {
+    fn ui(&mut self, ui: &mut Ui) {
+        let time = ui.input(|input| input.time) as f32;
+        let (columns, rows) = (self.columns, self.rows);
+
+        let pixel_coordinates = move || {
+            (0..rows)
+                .flat_map(move |row| (0..columns).map(move |column| (column as f32, row as f32)))
+        };
+
+        let led_matrix = LedMatrixDisplay::new(columns, rows)
+            .pixel_size(self.pixel_size)
+            .pixel_spacing(self.pixel_spacing)
+            .pixel_shape(self.pixel_shape)
+            .style(self.style);
+
+        let led_matrix = match self.content {
+            LedMatrixContent::Text => led_matrix.text(&self.text).scroll_speed(self.scroll_speed),
+            LedMatrixContent::Plasma => {
+                ui.ctx().request_repaint();
+                led_matrix.intensities(pixel_coordinates().map(|(x, y)| {
+                    let value = (x * 0.2 + time).sin()
+                        + (y * 0.3 - time * 1.3).sin()
+                        + ((x + y) * 0.15 + time * 0.7).sin();
+                    0.5 + value / 6.0
+                }))
+            }
+            LedMatrixContent::Rainbow => {
+                ui.ctx().request_repaint();
+                led_matrix.colors(pixel_coordinates().map(|(x, y)| {
+                    let hue = ((x + y) / (columns + rows) as f32 + time * 0.2).fract();
+                    Color32::from(Hsva::new(hue, 1.0, 1.0, 1.0))
+                }))
+            }
+        };
+
+        ui.add(led_matrix);
+        ui.separator();
+
+        Grid::new("led_matrix_display_properties")
+            .num_columns(2)
+            .spacing([20.0, 10.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Content");
+                ui.horizontal(|ui| {
+                    ui.selectable_value_from_iter(&mut self.content, LedMatrixContent::iter());
+                });
+                ui.end_row();
+
+                ui.label("Text");
+                ui.add(TextEdit::singleline(&mut self.text));
+                ui.end_row();
+
+                ui.label("Scroll speed");
+                ui.add(DragValue::new(&mut self.scroll_speed).range(0.0..=200.0));
+                ui.end_row();
+
+                ui.label("Columns");
+                ui.add(DragValue::new(&mut self.columns).range(1..=256));
+                ui.end_row();
+
+                ui.label("Rows");
+                ui.add(DragValue::new(&mut self.rows).range(1..=128));
+                ui.end_row();
+
+                ui.label("Pixel size");
+                ui.add(DragValue::new(&mut self.pixel_size));
+                ui.end_row();
+
+                ui.label("Pixel spacing");
+                ui.add(Slider::new(&mut self.pixel_spacing, 0.0..=1.0));
+                ui.end_row();
+
+                ui.label("Pixel shape");
+                ui.horizontal(|ui| {
+                    ui.selectable_value_from_iter(&mut self.pixel_shape, LedPixelShape::iter());
+                });
+                ui.end_row();
+
+                ui.label("Style");
+                display_style_ui(ui, &mut self.style, &mut self.style_preset);
+                ui.end_row();
+            });
+    }
+}
@@ -11,9 +11,15 @@ use angle_knob_page::AngleKnobPage;
 mod audio_knob_page;
 use audio_knob_page::AudioKnobPage;
 
+mod bar_graph_display_page;
+use bar_graph_display_page::BarGraphDisplayPage;
+
 mod barcode_page;
 use barcode_page::BarcodePage;
 
+mod character_display_page;
+use character_display_page::CharacterDisplayPage;
+
 mod datamatrix_page;
 use datamatrix_page::DataMatrixPage;
 
@@ -29,6 +35,9 @@ use indicator_button_page::IndicatorButtonPage;
 mod led_display_page;
 use led_display_page::LedDisplayPage;
 
+mod led_matrix_display_page;
+use led_matrix_display_page::LedMatrixDisplayPage;
+
 mod linear_compass_page;
 use linear_compass_page::LinearCompassPage;
 
@@ -41,6 +50,9 @@ use qrcode_page::QrCodePage;
 mod rotated_label_page;
 use rotated_label_page::RotatedLabelPage;
 
+mod segmented_display_editor_page;
+use segmented_display_editor_page::SegmentedDisplayEditorPage;
+
 mod segmented_display_page;
 use segmented_display_page::SegmentedDisplayPage;
 
@@ -70,10 +82,18 @@ pub enum PageId {
     #[strum(props(feature = "knobs"))]
     AudioKnobPage,
 
+    #[strum(to_string = "BarGraphDisplay")]
+    #[strum(props(feature = "displays"))]
+    BarGraphDisplayPage,
+
     #[strum(to_string = "BarcodeWidget")]
     #[strum(props(feature = "barcodes"))]
     BarcodePage,
 
+    #[strum(to_string = "CharacterDisplayWidget")]
+    #[strum(props(feature = "displays"))]
+    CharacterDisplayPage,
+
     #[strum(to_string = "DataMatrixWidget")]
     #[strum(props(feature = "barcodes"))]
     DataMatrixPage,
@@ -94,6 +114,10 @@ pub enum PageId {
     #[strum(props(feature = "displays"))]
     LedDisplayPage,
 
+    #[strum(to_string = "LedMatrixDisplay")]
+    #[strum(props(feature = "displays"))]
+    LedMatrixDisplayPage,
+
     #[strum(to_string = "LinearCompass")]
     #[strum(props(feature = "compasses"))]
     LinearCompassPage,
@@ -110,6 +134,10 @@ pub enum PageId {
     #[strum(props(feature = "ui"))]
     RotatedLabelPage,
 
+    #[strum(to_string = "SegmentedDisplayEditor")]
+    #[strum(props(feature = "displays"))]
+    SegmentedDisplayEditorPage,
+
     #[strum(to_string = "SegmentedDisplayWidget")]
     #[strum(props(feature = "displays"))]
     SegmentedDisplayPage,
@@ -131,16 +159,20 @@ impl PageId {
         match *self {
             PageId::AngleKnobPage => Box::<AngleKnobPage>::default(),
             PageId::AudioKnobPage => Box::<AudioKnobPage>::default(),
+            PageId::BarGraphDisplayPage => Box::<BarGraphDisplayPage>::default(),
             PageId::BarcodePage => Box::<BarcodePage>::default(),
+            PageId::CharacterDisplayPage => Box::<CharacterDisplayPage>::default(),
             PageId::DataMatrixPage => Box::<DataMatrixPage>::default(),
             PageId::DirectoryTreeViewPage => Box::<DirectoryTreeViewPage>::default(),
             PageId::HyperlinkWithIconPage => Box::<HyperlinkWithIconPage>::default(),
             PageId::IndicatorButtonPage => Box::<IndicatorButtonPage>::default(),
             PageId::LedDisplayPage => Box::<LedDisplayPage>::default(),
+            PageId::LedMatrixDisplayPage => Box::<LedMatrixDisplayPage>::default(),
             PageId::LinearCompassPage => Box::<LinearCompassPage>::default(),
             PageId::PolarCompassPage => Box::<PolarCompassPage>::default(),
             PageId::QrCodePage => Box::<QrCodePage>::default(),
             PageId::RotatedLabelPage => Box::<RotatedLabelPage>::default(),
+            PageId::SegmentedDisplayEditorPage => Box::<SegmentedDisplayEditorPage>::default(),
             PageId::SegmentedDisplayPage => Box::<SegmentedDisplayPage>::default(),
             PageId::StandardButtonsPage => Box::<StandardButtonsPage>::default(),
             PageId::ThumbstickWidgetPage => Box::<ThumbstickWidgetPage>::default(),
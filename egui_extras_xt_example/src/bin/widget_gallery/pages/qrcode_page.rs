@@ -1,6 +1,8 @@
 use eframe::egui::{DragValue, Grid, Ui};
 use eframe::epaint::Color32;
-use egui_extras_xt::barcodes::QrCodeWidget;
+use egui_extras_xt::barcodes::{ErrorPlaceholder, QrCodeWidget};
+use egui_extras_xt::ui::widgets_from_iter::ComboBoxFromIter;
+use strum::IntoEnumIterator;
 
 use crate::pages::PageImpl;
 
@@ -10,6 +12,7 @@ pub struct QrCodePage {
     quiet_zone: usize,
     foreground_color: Color32,
     background_color: Color32,
+    error_placeholder: ErrorPlaceholder,
 }
 
 impl Default for QrCodePage {
@@ -20,6 +23,7 @@ impl Default for QrCodePage {
             quiet_zone: 4,
             foreground_color: Color32::BLACK,
             background_color: Color32::WHITE,
+            error_placeholder: ErrorPlaceholder::Detailed,
         }
     }
 }
@@ -31,7 +35,8 @@ impl PageImpl for QrCodePage {
                 .module_size(self.module_size)
                 .quiet_zone(self.quiet_zone)
                 .foreground_color(self.foreground_color)
-                .background_color(self.background_color),
+                .background_color(self.background_color)
+                .error_placeholder(self.error_placeholder),
         );
         ui.separator();
 
@@ -59,6 +64,10 @@ impl PageImpl for QrCodePage {
                 ui.label("Background color");
                 ui.color_edit_button_srgba(&mut self.background_color);
                 ui.end_row();
+
+                ui.label("Error placeholder");
+                ui.combobox_from_iter("", &mut self.error_placeholder, ErrorPlaceholder::iter());
+                ui.end_row();
             });
     }
 }
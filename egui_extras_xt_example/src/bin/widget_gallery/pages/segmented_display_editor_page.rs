@@ -0,0 +1,105 @@
+use eframe::egui::{DragValue, Grid, Ui};
+use egui_extras_xt::displays::segmented_display::{NumberFormat, NumberRadix};
+use egui_extras_xt::displays::{
+    DisplayKind, DisplayStyle, DisplayStylePreset, SegmentedDisplayEditor, SegmentedDisplayWidget,
+};
+use egui_extras_xt::ui::widgets_from_iter::SelectableValueFromIter;
+use strum::IntoEnumIterator;
+
+use crate::pages::ui::display_style_ui;
+use crate::pages::PageImpl;
+
+pub struct SegmentedDisplayEditorPage {
+    integer_value: i64,
+    text_value: String,
+    selected_digit: Option<usize>,
+    digit_count: usize,
+    radix: NumberRadix,
+    leading_zeros: bool,
+    display_kind: DisplayKind,
+    style: DisplayStyle,
+    style_preset: DisplayStylePreset,
+}
+
+impl Default for SegmentedDisplayEditorPage {
+    fn default() -> SegmentedDisplayEditorPage {
+        SegmentedDisplayEditorPage {
+            integer_value: 1230,
+            text_value: "HELLO".to_owned(),
+            selected_digit: None,
+            digit_count: 4,
+            radix: NumberRadix::Decimal,
+            leading_zeros: true,
+            display_kind: DisplayKind::SevenSegment,
+            style: DisplayStylePreset::DeLoreanRed.style(),
+            style_preset: DisplayStylePreset::DeLoreanRed,
+        }
+    }
+}
+
+impl PageImpl for SegmentedDisplayEditorPage {
+    fn ui(&mut self, ui: &mut Ui) {
+        let (display_kind, style) = (self.display_kind, self.style);
+        let display = || {
+            SegmentedDisplayWidget::new(display_kind)
+                .digit_height(64.0)
+                .style(style)
+        };
+
+        ui.add(
+            SegmentedDisplayEditor::integer(
+                &mut self.integer_value,
+                NumberFormat::new(self.digit_count)
+                    .radix(self.radix)
+                    .leading_zeros(self.leading_zeros),
+            )
+            .display(display())
+            .selected_digit(&mut self.selected_digit),
+        );
+
+        ui.add(SegmentedDisplayEditor::text(&mut self.text_value, 8).display(display()));
+        ui.separator();
+
+        Grid::new("segmented_display_editor_properties")
+            .num_columns(2)
+            .spacing([20.0, 10.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Integer value");
+                ui.add(DragValue::new(&mut self.integer_value));
+                ui.end_row();
+
+                ui.label("Text value");
+                ui.text_edit_singleline(&mut self.text_value);
+                ui.end_row();
+
+                ui.label("Selected digit");
+                ui.label(format!("{:?}", self.selected_digit));
+                ui.end_row();
+
+                ui.label("Digit count");
+                ui.add(DragValue::new(&mut self.digit_count).range(1..=12));
+                ui.end_row();
+
+                ui.label("Radix");
+                ui.horizontal(|ui| {
+                    ui.selectable_value_from_iter(&mut self.radix, NumberRadix::iter());
+                });
+                ui.end_row();
+
+                ui.label("Leading zeros");
+                ui.checkbox(&mut self.leading_zeros, "");
+                ui.end_row();
+
+                ui.label("Display kind");
+                ui.horizontal(|ui| {
+                    ui.selectable_value_from_iter(&mut self.display_kind, DisplayKind::iter());
+                });
+                ui.end_row();
+
+                ui.label("Style");
+                display_style_ui(ui, &mut self.style, &mut self.style_preset);
+                ui.end_row();
+            });
+    }
+}
@@ -1,9 +1,12 @@
-use eframe::egui::{DragValue, Grid, Ui};
-use egui_extras_xt::displays::segmented_display::DisplayMetricsPreset;
+use eframe::egui::{DragValue, Grid, Slider, Ui};
+use egui_extras_xt::displays::segmented_display::{
+    DisplayBlink, DisplayFont, DisplayMetricsPreset, NumberFormat, NumberOverflow, NumberRadix,
+    ScrollMode,
+};
 use egui_extras_xt::displays::{
     DisplayKind, DisplayMetrics, DisplayStyle, DisplayStylePreset, SegmentedDisplayWidget,
 };
-use egui_extras_xt::ui::widgets_from_iter::SelectableValueFromIter;
+use egui_extras_xt::ui::widgets_from_iter::{ComboBoxFromIter, SelectableValueFromIter};
 use strum::IntoEnumIterator;
 
 use crate::pages::ui::{display_metrics_ui, display_style_ui};
@@ -11,6 +14,9 @@ use crate::pages::PageImpl;
 
 pub struct SegmentedDisplayPage {
     value: String,
+    numeric: bool,
+    number: f64,
+    number_format: NumberFormat,
     display_kind: DisplayKind,
     digit_height: f32,
     metrics: DisplayMetrics,
@@ -20,12 +26,25 @@ pub struct SegmentedDisplayPage {
     show_dots: bool,
     show_colons: bool,
     show_apostrophes: bool,
+    font_source: String,
+    fixed_digit_count: bool,
+    digit_count: usize,
+    scroll_mode: ScrollMode,
+    scroll_speed: f32,
+    blink_colons: bool,
+    blink_rate: f32,
+    digit_intensity: f32,
+    highlight_digit: Option<usize>,
+    highlight_preset: DisplayStylePreset,
 }
 
 impl Default for SegmentedDisplayPage {
     fn default() -> SegmentedDisplayPage {
         SegmentedDisplayPage {
             value: "EGUI_EXTRAS_XT".to_owned(),
+            numeric: false,
+            number: -273.15,
+            number_format: NumberFormat::new(8).decimals(3),
             display_kind: DisplayKind::SixteenSegment,
             digit_height: 80.0,
             metrics: DisplayMetricsPreset::Default.metrics(),
@@ -35,22 +54,62 @@ impl Default for SegmentedDisplayPage {
             show_dots: true,
             show_colons: true,
             show_apostrophes: true,
+            font_source: "# \"<char>\" = <glyph>\n".to_owned(),
+            fixed_digit_count: false,
+            digit_count: 8,
+            scroll_mode: ScrollMode::Scroll,
+            scroll_speed: 4.0,
+            blink_colons: false,
+            blink_rate: 1.0,
+            digit_intensity: 1.0,
+            highlight_digit: None,
+            highlight_preset: DisplayStylePreset::Amber,
         }
     }
 }
 
 impl PageImpl for SegmentedDisplayPage {
     fn ui(&mut self, ui: &mut Ui) {
-        ui.add(
-            SegmentedDisplayWidget::new(self.display_kind)
-                .digit_height(self.digit_height)
-                .metrics(self.metrics)
-                .style(self.style)
-                .show_dots(self.show_dots)
-                .show_colons(self.show_colons)
-                .show_apostrophes(self.show_apostrophes)
-                .push_string(&self.value),
-        );
+        let font = self.font_source.parse::<DisplayFont>();
+
+        let mut segmented_display = SegmentedDisplayWidget::new(self.display_kind)
+            .digit_height(self.digit_height)
+            .metrics(self.metrics)
+            .style(self.style)
+            .show_dots(self.show_dots)
+            .show_colons(self.show_colons)
+            .show_apostrophes(self.show_apostrophes)
+            .font(font.clone().unwrap_or_default())
+            .scroll_mode(self.scroll_mode)
+            .scroll_speed(self.scroll_speed)
+            .blink(if self.blink_colons {
+                DisplayBlink::COLON
+            } else {
+                DisplayBlink::NONE
+            })
+            .blink_rate(self.blink_rate)
+            .digit_intensity(self.digit_intensity);
+
+        segmented_display = if self.numeric {
+            segmented_display.push_decimal(self.number, self.number_format)
+        } else {
+            segmented_display.push_string(&self.value)
+        };
+
+        if let Some(highlight_digit) = self.highlight_digit {
+            let highlight_style = self.highlight_preset.style();
+            segmented_display = segmented_display.map_digits(|digit_index, digit| {
+                if digit_index == highlight_digit {
+                    digit.style = Some(highlight_style);
+                }
+            });
+        }
+
+        if self.fixed_digit_count {
+            segmented_display = segmented_display.digit_count(self.digit_count);
+        }
+
+        ui.add(segmented_display);
         ui.separator();
 
         Grid::new("segmented_display_properties")
@@ -59,7 +118,48 @@ impl PageImpl for SegmentedDisplayPage {
             .striped(true)
             .show(ui, |ui| {
                 ui.label("Value");
-                ui.text_edit_singleline(&mut self.value);
+                ui.add_enabled_ui(!self.numeric, |ui| {
+                    ui.text_edit_singleline(&mut self.value);
+                });
+                ui.end_row();
+
+                ui.label("Numeric");
+                ui.checkbox(&mut self.numeric, "");
+                ui.end_row();
+
+                ui.add_enabled_ui(self.numeric, |ui| ui.label("Number"));
+                ui.add_enabled_ui(self.numeric, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.add(DragValue::new(&mut self.number).speed(0.01));
+                        ui.label("Digits");
+                        ui.add(DragValue::new(&mut self.number_format.digit_count));
+                        ui.label("Decimals");
+                        ui.add(DragValue::new(&mut self.number_format.decimals));
+                        ui.checkbox(&mut self.number_format.leading_zeros, "Leading zeros");
+                    });
+                });
+                ui.end_row();
+
+                ui.add_enabled_ui(self.numeric, |ui| ui.label("Radix"));
+                ui.add_enabled_ui(self.numeric, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.selectable_value_from_iter(
+                            &mut self.number_format.radix,
+                            NumberRadix::iter(),
+                        );
+                    });
+                });
+                ui.end_row();
+
+                ui.add_enabled_ui(self.numeric, |ui| ui.label("Overflow"));
+                ui.add_enabled_ui(self.numeric, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.selectable_value_from_iter(
+                            &mut self.number_format.overflow,
+                            NumberOverflow::iter(),
+                        );
+                    });
+                });
                 ui.end_row();
 
                 ui.label("Display kind");
@@ -91,6 +191,68 @@ impl PageImpl for SegmentedDisplayPage {
                 ui.label("Show apostrophes");
                 ui.checkbox(&mut self.show_apostrophes, "");
                 ui.end_row();
+
+                ui.label("Digit count");
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.fixed_digit_count, "");
+                    ui.add_enabled(
+                        self.fixed_digit_count,
+                        DragValue::new(&mut self.digit_count).range(1..=32),
+                    );
+                });
+                ui.end_row();
+
+                ui.label("Scroll mode");
+                ui.horizontal(|ui| {
+                    ui.selectable_value_from_iter(&mut self.scroll_mode, ScrollMode::iter());
+                });
+                ui.end_row();
+
+                ui.label("Scroll speed");
+                ui.add(DragValue::new(&mut self.scroll_speed).speed(0.1));
+                ui.end_row();
+
+                ui.label("Blink colons");
+                ui.checkbox(&mut self.blink_colons, "");
+                ui.end_row();
+
+                ui.label("Blink rate");
+                ui.add(DragValue::new(&mut self.blink_rate).speed(0.1));
+                ui.end_row();
+
+                ui.label("Digit intensity");
+                ui.add(Slider::new(&mut self.digit_intensity, 0.0..=1.0));
+                ui.end_row();
+
+                ui.label("Highlight digit");
+                ui.horizontal(|ui| {
+                    let mut highlight = self.highlight_digit.is_some();
+                    ui.checkbox(&mut highlight, "");
+
+                    let mut highlight_digit = self.highlight_digit.unwrap_or_default();
+                    ui.add_enabled(highlight, DragValue::new(&mut highlight_digit));
+                    self.highlight_digit = highlight.then_some(highlight_digit);
+
+                    ui.add_enabled_ui(highlight, |ui| {
+                        ui.push_id("highlight_preset_combo", |ui| {
+                            ui.combobox_from_iter(
+                                "",
+                                &mut self.highlight_preset,
+                                DisplayStylePreset::iter(),
+                            );
+                        });
+                    });
+                });
+                ui.end_row();
+
+                ui.label("Custom font");
+                ui.vertical(|ui| {
+                    ui.code_editor(&mut self.font_source);
+                    if let Err(error) = font {
+                        ui.colored_label(ui.style().visuals.error_fg_color, error.to_string());
+                    }
+                });
+                ui.end_row();
             });
     }
 }
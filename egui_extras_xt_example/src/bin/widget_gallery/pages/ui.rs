@@ -1,13 +1,13 @@
 use std::path::PathBuf;
 use std::str::FromStr;
 
-use eframe::egui::{DragValue, Grid, Response, TextEdit, Ui};
+use eframe::egui::{DragValue, Grid, Response, TextEdit, Theme, Ui};
 use eframe::epaint::Color32;
 
 use egui_extras_xt::common::{Orientation, WidgetShape};
 use egui_extras_xt::compasses::{CompassMarkerShape, DefaultCompassMarkerColor};
 use egui_extras_xt::displays::segmented_display::DisplayMetricsPreset;
-use egui_extras_xt::displays::{DisplayMetrics, DisplayStyle, DisplayStylePreset};
+use egui_extras_xt::displays::{DisplayEffects, DisplayMetrics, DisplayStyle, DisplayStylePreset};
 use egui_extras_xt::knobs::{ThumbstickDeadZone, ThumbstickSnap};
 use egui_extras_xt::ui::standard_buttons::StandardButtons;
 use egui_extras_xt::ui::widgets_from_iter::ComboBoxFromIter;
@@ -32,16 +32,26 @@ pub fn display_style_ui(
                         .combobox_from_iter("", style_preset, DisplayStylePreset::iter())
                         .changed()
                     {
-                        *style = style_preset.style();
+                        *style = style_preset.themed_style(ui);
                     }
 
                     if ui.reset_button().clicked() {
-                        *style = style_preset.style();
+                        *style = style_preset.themed_style(ui);
                     }
                 });
             });
             ui.end_row();
 
+            ui.label("Theme variant");
+            ui.horizontal(|ui| {
+                for theme in [Theme::Light, Theme::Dark] {
+                    if ui.button(format!("{theme:?}")).clicked() {
+                        *style = style_preset.style_for_theme(theme);
+                    }
+                }
+            });
+            ui.end_row();
+
             ui.label("Background color");
             ui.color_edit_button_srgba(&mut style.background_color);
             ui.end_row();
@@ -67,6 +77,46 @@ pub fn display_style_ui(
                 ui.add(DragValue::new(&mut style.active_foreground_stroke.width));
             });
             ui.end_row();
+
+            ui.label("Effects");
+            ui.horizontal(|ui| {
+                if ui.button("Preset").clicked() {
+                    style.effects = style_preset.effects();
+                }
+
+                if ui.button("None").clicked() {
+                    style.effects = DisplayEffects::NONE;
+                }
+            });
+            ui.end_row();
+
+            ui.label("Glow");
+            ui.horizontal(|ui| {
+                ui.add(DragValue::new(&mut style.effects.glow_radius).range(0.0..=f32::INFINITY));
+                ui.add(
+                    DragValue::new(&mut style.effects.glow_intensity)
+                        .range(0.0..=1.0)
+                        .speed(0.01),
+                );
+            });
+            ui.end_row();
+
+            ui.label("Persistence");
+            ui.add(
+                DragValue::new(&mut style.effects.persistence)
+                    .range(0.0..=f32::INFINITY)
+                    .speed(0.01)
+                    .suffix(" s"),
+            );
+            ui.end_row();
+
+            ui.label("Reflection");
+            ui.add(
+                DragValue::new(&mut style.effects.reflection)
+                    .range(0.0..=1.0)
+                    .speed(0.01),
+            );
+            ui.end_row();
         });
 }
 